@@ -0,0 +1,145 @@
+//! Module for generating self-contained, shields-style SVG badges from MCP server information.
+
+use html_escape::encode_text;
+
+use crate::{utils::slugify, McpServerInfo};
+
+/// Badge color used for informational values such as versions.
+pub const BADGE_COLOR_INFO: &str = "#007ec6";
+/// Badge color used for supported capabilities.
+pub const BADGE_COLOR_SUCCESS: &str = "#4c1";
+/// Badge color used for unsupported or empty capabilities.
+pub const BADGE_COLOR_INACTIVE: &str = "#9f9f9f";
+
+const BADGE_LABEL_COLOR: &str = "#555";
+const BADGE_HEIGHT: usize = 20;
+const BADGE_TEXT_PADDING: usize = 10;
+
+/// Estimates the rendered width of a text in pixels, for 11px Verdana.
+fn text_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | 'I' | '.' | ',' | ':' | ';' | '!' | '|' | '\'' | ' ' => 4,
+            'f' | 'r' | 't' | '(' | ')' | '[' | ']' | '-' => 5,
+            'm' | 'w' | 'M' | 'W' | '@' => 10,
+            c if c.is_ascii_uppercase() => 8,
+            _ => 7,
+        })
+        .sum()
+}
+
+/// Generates a flat, shields-style SVG badge with a label and a value.
+///
+/// Element ids are derived from the label, so several badges can be inlined into the same document.
+pub fn badge_svg(label: &str, value: &str, color: &str) -> String {
+    let label_width = text_width(label) + BADGE_TEXT_PADDING;
+    let value_width = text_width(value) + BADGE_TEXT_PADDING;
+    let width = label_width + value_width;
+    let label_x = label_width as f32 / 2.0;
+    let value_x = label_width as f32 + value_width as f32 / 2.0;
+
+    let id = format!("badge-{}", slugify(label));
+    let label = encode_text(label);
+    let value = encode_text(value);
+    let color = encode_text(color);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{BADGE_HEIGHT}" role="img" aria-label="{label}: {value}"><title>{label}: {value}</title><linearGradient id="{id}-s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="{id}-r"><rect width="{width}" height="{BADGE_HEIGHT}" rx="3" fill="#fff"/></clipPath><g clip-path="url(#{id}-r)"><rect width="{label_width}" height="{BADGE_HEIGHT}" fill="{BADGE_LABEL_COLOR}"/><rect x="{label_width}" width="{value_width}" height="{BADGE_HEIGHT}" fill="{color}"/><rect width="{width}" height="{BADGE_HEIGHT}" fill="url(#{id}-s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text><text x="{value_x}" y="15" fill="#010101" fill-opacity=".3">{value}</text><text x="{value_x}" y="14">{value}</text></g></svg>"##
+    )
+}
+
+/// Builds a capability badge, showing the number of items when the capability is supported.
+fn capability_badge(label: &str, supported: bool, count: Option<usize>) -> (String, String) {
+    let (value, color) = match (supported, count.unwrap_or_default()) {
+        (true, count) if count > 0 => (count.to_string(), BADGE_COLOR_SUCCESS),
+        (true, _) => ("0".to_string(), BADGE_COLOR_INACTIVE),
+        (false, _) => ("none".to_string(), BADGE_COLOR_INACTIVE),
+    };
+    (slugify(label), badge_svg(label, &value, color))
+}
+
+/// Generates the standard set of badges for a server, as `(file stem, svg)` pairs.
+pub fn server_badges(server_info: &McpServerInfo) -> Vec<(String, String)> {
+    let caps = &server_info.capabilities;
+
+    let mut badges = vec![(
+        "version".to_string(),
+        badge_svg("version", &server_info.version, BADGE_COLOR_INFO),
+    )];
+
+    if let Some(protocol_version) = &server_info.protocol_version {
+        badges.push((
+            "protocol".to_string(),
+            badge_svg("protocol", protocol_version, BADGE_COLOR_INFO),
+        ));
+    }
+
+    badges.extend([
+        capability_badge(
+            "tools",
+            caps.tools,
+            server_info.tools.as_ref().map(|t| t.len()),
+        ),
+        capability_badge(
+            "prompts",
+            caps.prompts,
+            server_info.prompts.as_ref().map(|p| p.len()),
+        ),
+        capability_badge(
+            "resources",
+            caps.resources,
+            server_info.resources.as_ref().map(|r| r.len()),
+        ),
+        capability_badge(
+            "resource templates",
+            caps.resources,
+            server_info.resource_templates.as_ref().map(|r| r.len()),
+        ),
+    ]);
+
+    badges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+
+    #[test]
+    fn test_badge_svg() {
+        let svg = badge_svg("tools", "14", BADGE_COLOR_SUCCESS);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="64""#));
+        assert!(svg.contains("<title>tools: 14</title>"));
+        assert!(svg.contains(r##"<rect x="40" width="24" height="20" fill="#4c1"/>"##));
+        assert!(svg.contains(r#"<clipPath id="badge-tools-r">"#));
+
+        let svg = badge_svg("a<b", "c&d", BADGE_COLOR_INFO);
+        assert!(svg.contains("<title>a&lt;b: c&amp;d</title>"));
+    }
+
+    #[test]
+    fn test_server_badges() {
+        let mut server_info = sample_server_info().unwrap();
+        server_info.capabilities.prompts = false;
+        server_info.prompts = None;
+        server_info.resources = Some(vec![]);
+
+        let badges = server_badges(&server_info);
+        let names: Vec<_> = badges.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "version",
+                "protocol",
+                "tools",
+                "prompts",
+                "resources",
+                "resource-templates"
+            ]
+        );
+        assert!(badges[1].1.contains("<title>protocol: 2025-11-25</title>"));
+        assert!(badges[2].1.contains("<title>tools: 2</title>"));
+        assert!(badges[3].1.contains("<title>prompts: none</title>"));
+        assert!(badges[4].1.contains(BADGE_COLOR_INACTIVE));
+    }
+}
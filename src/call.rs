@@ -0,0 +1,475 @@
+//! Module for calling a tool of an MCP server with arguments given on the command line,
+//! and printing its result.
+
+use std::collections::HashSet;
+
+use colored::Colorize;
+use rust_mcp_sdk::schema::{
+    BlobResourceContents, CallToolResult, ContentBlock, EmbeddedResourceResource,
+    TextResourceContents,
+};
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{DiscoveryError, DiscoveryResult},
+    schema::resolve_ref,
+    McpToolMeta, ParamTypes,
+};
+
+fn is_string_type(param_type: &ParamTypes) -> bool {
+    matches!(param_type, ParamTypes::Primitive(type_name) if type_name == "string")
+}
+
+/// Converts the text value of a `--arg key=value` pair to the JSON value expected by a parameter.
+pub fn coerce_arg(value: &str, param_type: &ParamTypes) -> Result<Value, String> {
+    match param_type {
+        ParamTypes::Primitive(type_name) => match type_name.as_str() {
+            "string" => Ok(Value::String(value.to_string())),
+            "integer" => value
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| format!("expected an integer, got '{value}'")),
+            "number" => value
+                .parse::<f64>()
+                .ok()
+                .and_then(|number| serde_json::Number::from_f64(number).map(Value::Number))
+                .ok_or_else(|| format!("expected a number, got '{value}'")),
+            "boolean" => value
+                .parse::<bool>()
+                .map(Value::Bool)
+                .map_err(|_| format!("expected true or false, got '{value}'")),
+            "null" if value == "null" => Ok(Value::Null),
+            "null" => Err(format!("expected null, got '{value}'")),
+            // unknown types are passed as JSON when possible, as text otherwise
+            _ => Ok(
+                serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()))
+            ),
+        },
+        ParamTypes::Object(_) | ParamTypes::AllOf(_) => match serde_json::from_str(value) {
+            Ok(Value::Object(object)) => Ok(Value::Object(object)),
+            _ => Err(format!("expected a JSON object, got '{value}'")),
+        },
+        ParamTypes::Array(items) => match serde_json::from_str(value) {
+            Ok(Value::Array(array)) => Ok(Value::Array(array)),
+            // a comma separated list, with items coerced to the type of the array items
+            _ => value
+                .split(',')
+                .map(|item| match items.first() {
+                    Some(item_type) => coerce_arg(item.trim(), item_type),
+                    None => Ok(Value::String(item.trim().to_string())),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+        },
+        ParamTypes::Anyof(variants) | ParamTypes::OneOf(variants) => {
+            // text matches any string variant, so other variants are tried first
+            variants
+                .iter()
+                .filter(|variant| !is_string_type(variant))
+                .chain(variants.iter().filter(|variant| is_string_type(variant)))
+                .find_map(|variant| coerce_arg(value, variant).ok())
+                .ok_or_else(|| format!("expected {param_type}, got '{value}'"))
+        }
+        // enum values are only known as text, so numbers and booleans are recognized by their JSON form
+        ParamTypes::EnumValues(_) => match serde_json::from_str(value) {
+            Ok(value @ (Value::Number(_) | Value::Bool(_) | Value::Null)) => Ok(value),
+            _ => Ok(Value::String(value.to_string())),
+        },
+    }
+}
+
+/// Builds the arguments of a tool call from a JSON object and `key=value` pairs,
+/// the pairs taking precedence over the JSON object.
+pub fn build_arguments(
+    tool: &McpToolMeta,
+    args_json: Option<&str>,
+    args: &[(String, String)],
+) -> DiscoveryResult<Map<String, Value>> {
+    let mut arguments = match args_json.map(serde_json::from_str::<Value>).transpose() {
+        Ok(None) => Map::new(),
+        Ok(Some(Value::Object(arguments))) => arguments,
+        Ok(Some(_)) => {
+            return Err(DiscoveryError::InvalidArguments(
+                "--args must be a JSON object".to_string(),
+            ))
+        }
+        Err(error) => {
+            return Err(DiscoveryError::InvalidArguments(format!(
+                "--args is not valid JSON: {error}"
+            )))
+        }
+    };
+
+    for (key, value) in args.iter() {
+        let param = tool
+            .params
+            .iter()
+            .find(|param| &param.param_name == key)
+            .ok_or_else(|| {
+                DiscoveryError::InvalidArguments(format!(
+                    "Tool '{}' has no '{key}' parameter. Expected one of: {}",
+                    tool.name,
+                    tool.params
+                        .iter()
+                        .map(|param| param.param_name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?;
+        let value = coerce_arg(value, &param.param_type).map_err(|reason| {
+            DiscoveryError::InvalidArguments(format!("Invalid value for '{key}': {reason}"))
+        })?;
+        arguments.insert(key.to_owned(), value);
+    }
+
+    Ok(arguments)
+}
+
+/// Returns the name of the JSON type of a value, as used by JSON Schema.
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &Value, type_name: &str) -> bool {
+    match (type_name, json_type(value)) {
+        ("number", "integer") => true,
+        ("integer", "number") => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+        (expected, actual) => expected == actual,
+    }
+}
+
+/// Validates a value against a JSON schema, collecting a problem for each mismatch.
+///
+/// Covers the keywords used by tool input schemas: `type`, `enum`, `const`, `required`, `properties`,
+/// `additionalProperties`, `items`, `anyOf`, `oneOf`, `allOf` and local `$ref` references.
+fn validate_value(
+    schema: &Value,
+    root_schema: &Value,
+    value: &Value,
+    path: &str,
+    problems: &mut Vec<String>,
+) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(ref_path) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve_ref(ref_path, root_schema, &mut HashSet::new()) {
+            Ok(target) => validate_value(target, root_schema, value, path, problems),
+            Err(error) => problems.push(format!("{path}: {error}")),
+        }
+        return;
+    }
+
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::String(type_name) => vec![type_name.as_str()],
+            Value::Array(type_names) => type_names.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|type_name| matches_type(value, type_name)) {
+            problems.push(format!(
+                "{path}: expected {}, got {}",
+                types.join(" or "),
+                json_type(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        if !values.contains(value) {
+            problems.push(format!(
+                "{path}: expected one of {}, got {value}",
+                Value::Array(values.to_owned())
+            ));
+        }
+    }
+
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            problems.push(format!("{path}: expected {constant}, got {value}"));
+        }
+    }
+
+    if let Value::Object(object) = value {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(required) {
+                problems.push(format!("{path}: missing required property '{required}'"));
+            }
+        }
+        for (key, property_value) in object.iter() {
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(property_schema) => validate_value(
+                    property_schema,
+                    root_schema,
+                    property_value,
+                    &format!("{path}.{key}"),
+                    problems,
+                ),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    problems.push(format!("{path}: unknown property '{key}'"));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(items_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate_value(
+                items_schema,
+                root_schema,
+                item,
+                &format!("{path}[{index}]"),
+                problems,
+            );
+        }
+    }
+
+    let matching_variants = |keyword: &str| {
+        schema
+            .get(keyword)
+            .and_then(Value::as_array)
+            .map(|variants| {
+                let matching = variants
+                    .iter()
+                    .filter(|variant| {
+                        let mut variant_problems = vec![];
+                        validate_value(variant, root_schema, value, path, &mut variant_problems);
+                        variant_problems.is_empty()
+                    })
+                    .count();
+                (matching, variants.len())
+            })
+    };
+    if let Some((0, _)) = matching_variants("anyOf") {
+        problems.push(format!("{path}: does not match any of the 'anyOf' schemas"));
+    }
+    if let Some((matching, _)) = matching_variants("oneOf").filter(|(matching, _)| *matching != 1) {
+        problems.push(format!(
+            "{path}: matches {matching} of the 'oneOf' schemas, expected exactly one"
+        ));
+    }
+    if let Some(variants) = schema.get("allOf").and_then(Value::as_array) {
+        for variant in variants.iter() {
+            validate_value(variant, root_schema, value, path, problems);
+        }
+    }
+}
+
+/// Validates the arguments of a tool call against the input schema of the tool.
+/// Returns the problems found, which is empty for valid arguments.
+pub fn validate_arguments(tool: &McpToolMeta, arguments: &Map<String, Value>) -> Vec<String> {
+    let input_schema = serde_json::to_value(&tool.input_schema).unwrap_or_default();
+    let mut problems = vec![];
+    validate_value(
+        &input_schema,
+        &input_schema,
+        &Value::Object(arguments.to_owned()),
+        "arguments",
+        &mut problems,
+    );
+    problems
+}
+
+/// Returns the approximate size in bytes of base64 encoded data.
+fn decoded_size(data: &str) -> usize {
+    let padding = data.bytes().rev().take_while(|byte| *byte == b'=').count();
+    (data.len() * 3 / 4).saturating_sub(padding)
+}
+
+/// Formats the contents of a text resource, as a header line followed by the text.
+pub fn format_text_resource(resource: &TextResourceContents) -> Vec<String> {
+    vec![
+        format!(
+            "{} {}{}",
+            "[resource]".cyan(),
+            resource.uri,
+            resource
+                .mime_type
+                .as_ref()
+                .map_or(String::new(), |mime_type| format!(" ({mime_type})"))
+        ),
+        resource.text.to_owned(),
+    ]
+}
+
+/// Formats the metadata of a binary resource, without its content.
+pub fn format_blob_resource(resource: &BlobResourceContents) -> Vec<String> {
+    vec![format!(
+        "{} {}{}, {} bytes",
+        "[resource]".cyan(),
+        resource.uri,
+        resource
+            .mime_type
+            .as_ref()
+            .map_or(String::new(), |mime_type| format!(" ({mime_type})")),
+        decoded_size(&resource.blob)
+    )]
+}
+
+/// Formats a content block for the terminal: text as-is, metadata for binary content and resources.
+pub fn format_content_block(block: &ContentBlock) -> Vec<String> {
+    match block {
+        ContentBlock::TextContent(text) => vec![text.text.to_owned()],
+        ContentBlock::ImageContent(image) => vec![format!(
+            "{} {}, {} bytes",
+            "[image]".cyan(),
+            image.mime_type,
+            decoded_size(&image.data)
+        )],
+        ContentBlock::AudioContent(audio) => vec![format!(
+            "{} {}, {} bytes",
+            "[audio]".cyan(),
+            audio.mime_type,
+            decoded_size(&audio.data)
+        )],
+        ContentBlock::ResourceLink(link) => {
+            let mut lines = vec![format!(
+                "{} {} <{}>{}",
+                "[resource link]".cyan(),
+                link.name,
+                link.uri,
+                link.mime_type
+                    .as_ref()
+                    .map_or(String::new(), |mime_type| format!(" ({mime_type})"))
+            )];
+            if let Some(description) = &link.description {
+                lines.push(description.dimmed().to_string());
+            }
+            lines
+        }
+        ContentBlock::EmbeddedResource(embedded) => match &embedded.resource {
+            EmbeddedResourceResource::TextResourceContents(resource) => {
+                format_text_resource(resource)
+            }
+            EmbeddedResourceResource::BlobResourceContents(resource) => {
+                format_blob_resource(resource)
+            }
+        },
+    }
+}
+
+/// Formats the result of a tool call for the terminal: its content blocks,
+/// followed by the structured content as pretty-printed JSON.
+pub fn format_call_result(result: &CallToolResult) -> String {
+    let mut lines: Vec<String> = vec![];
+
+    if result.is_error == Some(true) {
+        lines.push("The tool reported an error:".red().bold().to_string());
+    }
+
+    lines.extend(result.content.iter().flat_map(format_content_block));
+
+    if let Some(structured_content) = &result.structured_content {
+        lines.push("Structured content:".bold().to_string());
+        lines.push(serde_json::to_string_pretty(structured_content).unwrap_or_default());
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_arguments() {
+        let tools = sample_server_info().unwrap().tools.unwrap();
+        let search = &tools[0];
+
+        let args = [
+            ("query", "bug"),
+            ("limit", "10"),
+            ("labels", "ui, crash"),
+            ("author", r#"{"name": "ana"}"#),
+            ("since", "42"),
+            ("order", "desc"),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+        let arguments =
+            build_arguments(search, Some(r#"{"query": "x", "format": "json"}"#), &args).unwrap();
+        assert_eq!(
+            Value::Object(arguments.clone()),
+            json!({
+                "query": "bug", "limit": 10, "labels": ["ui", "crash"], "author": { "name": "ana" },
+                "since": 42, "order": "desc", "format": "json"
+            })
+        );
+        assert!(validate_arguments(search, &arguments).is_empty());
+
+        let error = build_arguments(search, None, &[("limit".into(), "ten".into())]).unwrap_err();
+        assert!(error.to_string().contains("expected an integer"), "{error}");
+
+        let error = build_arguments(search, None, &[("qeury".into(), "x".into())]).unwrap_err();
+        assert!(
+            error.to_string().contains("no 'qeury' parameter"),
+            "{error}"
+        );
+
+        assert!(build_arguments(search, Some("[1]"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_validate_arguments() {
+        let tools = sample_server_info().unwrap().tools.unwrap();
+        let search = &tools[0];
+
+        let arguments = json!({
+            "limit": "10",
+            "labels": ["ui", 3],
+            "author": {},
+            "order": "up",
+            "format": "xml"
+        });
+        let problems = validate_arguments(search, arguments.as_object().unwrap());
+        assert_eq!(
+            problems,
+            vec![
+                "arguments: missing required property 'query'",
+                "arguments.author: missing required property 'name'",
+                "arguments.format: matches 0 of the 'oneOf' schemas, expected exactly one",
+                "arguments.labels[1]: expected string, got integer",
+                "arguments.limit: expected integer, got string",
+                "arguments.order: expected one of [\"asc\",\"desc\"], got \"up\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_call_result() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [
+                { "type": "text", "text": "Found 2 issues" },
+                { "type": "image", "data": "aGVsbG8=", "mimeType": "image/png" },
+                { "type": "resource", "resource": { "uri": "issues://1", "mimeType": "text/plain", "text": "First issue" } }
+            ],
+            "structuredContent": { "count": 2 }
+        }))
+        .unwrap();
+
+        let output = strip_ansi_escapes::strip_str(format_call_result(&result));
+        assert_eq!(
+            output,
+            "Found 2 issues\n[image] image/png, 5 bytes\n[resource] issues://1 (text/plain)\nFirst issue\nStructured content:\n{\n  \"count\": 2\n}"
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use clap::{Parser, Subcommand, ValueEnum};
-use mcp_discovery::{DiscoveryCommand, LogLevel, PrintOptions, Template, WriteOptions};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use mcp_discovery::{
+    BadgeOptions, CallOptions, DiffFormat, DiscoveryCommand, DiscoveryOptions, ExploreOptions,
+    ExportOptions, LogLevel, OutputOptions, PrintOptions, SiteOptions, Template, TemplateOptions,
+    TemplatesCommand, UpdateOptions, ValidateOptions, WriteOptions,
+};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, ValueEnum, PartialEq)]
@@ -8,6 +12,11 @@ pub enum CliTemplate {
     MdPlain,
     Html,
     Txt,
+    Man,
+    Mermaid,
+    Dot,
+    Llms,
+    LlmsFull,
 }
 
 impl From<CliTemplate> for Template {
@@ -17,6 +26,26 @@ impl From<CliTemplate> for Template {
             CliTemplate::MdPlain => Self::MdPlain,
             CliTemplate::Html => Self::Html,
             CliTemplate::Txt => Self::Txt,
+            CliTemplate::Man => Self::Man,
+            CliTemplate::Mermaid => Self::Mermaid,
+            CliTemplate::Dot => Self::Dot,
+            CliTemplate::Llms => Self::Llms,
+            CliTemplate::LlmsFull => Self::LlmsFull,
+        }
+    }
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum CliDiffFormat {
+    Unified,
+    Json,
+}
+
+impl From<CliDiffFormat> for DiffFormat {
+    fn from(value: CliDiffFormat) -> Self {
+        match value {
+            CliDiffFormat::Unified => Self::Unified,
+            CliDiffFormat::Json => Self::Json,
         }
     }
 }
@@ -49,8 +78,210 @@ pub enum CliDiscoveryCommand {
     Print(CliPrintOptions),
     /// Creates a file with MCP server capability details.
     Create(CliWriteOptions),
-    /// Updates a file by adding MCP server capability information between specified markers.
-    Update(CliWriteOptions),
+    /// Updates files by adding MCP server capability information between specified markers.
+    Update(CliUpdateOptions),
+    /// Generates a multi-page static HTML documentation site in a directory.
+    Site(CliSiteOptions),
+    /// Writes SVG badges summarizing MCP server capabilities to a directory.
+    Badges(CliBadgeOptions),
+    /// Calls a tool of the MCP server and prints its result.
+    Call(CliCallOptions),
+    /// Starts an interactive session to browse tools, prompts and resources, call tools,
+    /// read resources and get prompts.
+    Explore(CliExploreOptions),
+    /// Manages built-in templates, without launching an MCP server.
+    #[command(subcommand)]
+    Templates(CliTemplatesCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CliTemplatesCommand {
+    /// Writes a built-in template and its partials to a directory for customization.
+    Export(CliExportOptions),
+    /// Checks a custom template by rendering it against a sample server, without launching an MCP server.
+    Validate(CliValidateOptions),
+}
+
+impl From<CliTemplatesCommand> for TemplatesCommand {
+    fn from(value: CliTemplatesCommand) -> Self {
+        match value {
+            CliTemplatesCommand::Export(cli_export_options) => {
+                Self::Export(cli_export_options.into())
+            }
+            CliTemplatesCommand::Validate(cli_validate_options) => {
+                Self::Validate(cli_validate_options.into())
+            }
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExportOptions {
+    /// Built-in template to export.
+    #[arg(value_enum)]
+    pub template: CliTemplate,
+
+    /// Directory where the template and its partials will be written.
+    #[arg(short, long)]
+    pub out: PathBuf,
+
+    /// Overwrite files that already exist in the output directory.
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliValidateOptions {
+    /// Path to the Handlebars template file to validate.
+    pub template_file: PathBuf,
+
+    #[command(flatten)]
+    pub templating: CliTemplateOptions,
+
+    /// Render missing fields and helpers as empty text instead of reporting them. Templates are validated in strict mode by default.
+    #[arg(long, conflicts_with = "strict")]
+    pub no_strict: bool,
+}
+
+impl From<CliValidateOptions> for ValidateOptions {
+    fn from(value: CliValidateOptions) -> Self {
+        Self {
+            template_file: value.template_file,
+            templating: TemplateOptions {
+                strict: !value.no_strict,
+                ..value.templating.into()
+            },
+        }
+    }
+}
+
+impl From<CliExportOptions> for ExportOptions {
+    fn from(value: CliExportOptions) -> Self {
+        Self {
+            template: value.template.into(),
+            out_dir: value.out,
+            force: value.force,
+        }
+    }
+}
+
+/// Parses a `KEY=VALUE` template variable definition.
+fn parse_define(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("invalid definition '{value}', expected KEY=VALUE")),
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct CliDiscoveryOptions {
+    /// Read the content of each listed resource with `resources/read`, storing a preview of its text,
+    /// or the size and hash of binary content, for templates to render.
+    #[arg(long)]
+    pub read_resources: bool,
+
+    /// Maximum number of bytes kept in the text preview of a resource. Longer text is truncated.
+    #[arg(
+        long,
+        value_name = "BYTES",
+        default_value_t = 1024,
+        requires = "read_resources"
+    )]
+    pub resource_size_limit: usize,
+
+    /// Only read resources with a URI matching one of these comma separated patterns.
+    /// Patterns are globs supporting `*` and `?`, or regular expressions enclosed in slashes.
+    #[arg(long, value_name = "PATTERNS", requires = "read_resources")]
+    pub resource_include: Option<String>,
+
+    /// Skip resources with a URI matching one of these comma separated patterns.
+    #[arg(long, value_name = "PATTERNS", requires = "read_resources")]
+    pub resource_exclude: Option<String>,
+
+    /// Get each prompt with `prompts/get`, storing the messages it expands to for templates to show
+    /// a sample conversation. Required arguments without a sample value are set to `<name>` placeholders.
+    #[arg(long)]
+    pub prompt_samples: bool,
+
+    /// JSON or YAML file of sample prompt arguments, as an object of argument values for each prompt name.
+    #[arg(long, value_name = "FILE", requires = "prompt_samples")]
+    pub prompt_args: Option<PathBuf>,
+}
+
+impl From<CliDiscoveryOptions> for DiscoveryOptions {
+    fn from(value: CliDiscoveryOptions) -> Self {
+        Self {
+            read_resources: value.read_resources,
+            resource_size_limit: value.resource_size_limit,
+            resource_include: value.resource_include,
+            resource_exclude: value.resource_exclude,
+            prompt_samples: value.prompt_samples,
+            prompt_args_file: value.prompt_args,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct CliTemplateOptions {
+    /// Directory of `*.hbs` files to register as partials, overriding built-in partials with the same name.
+    #[arg(long)]
+    pub partials_dir: Option<PathBuf>,
+
+    /// Fail on missing fields and helpers in templates instead of rendering them as empty text.
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Define a template variable, available as `vars.<KEY>`. Can be repeated.
+    #[arg(long = "define", value_name = "KEY=VALUE", value_parser = parse_define)]
+    pub defines: Vec<(String, String)>,
+
+    /// JSON or YAML file of template variables, available under `vars`.
+    #[arg(long)]
+    pub vars_file: Option<PathBuf>,
+}
+
+impl From<CliTemplateOptions> for TemplateOptions {
+    fn from(value: CliTemplateOptions) -> Self {
+        Self {
+            partials_dir: value.partials_dir,
+            strict: value.strict,
+            defines: value.defines,
+            vars_file: value.vars_file,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct CliOutputOptions {
+    /// Print the changes as a diff instead of writing the file, or the full output for a new file.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Format used to print the changes of a dry run.
+    #[arg(long, value_enum, default_value = "unified", requires = "dry_run")]
+    pub diff_format: CliDiffFormat,
+
+    /// Keep a copy of the overwritten file, with a `.bak` extension appended.
+    #[arg(long)]
+    pub backup: bool,
+
+    /// Watch paths for changes, such as the server sources, re-running discovery and rendering on change.
+    /// Template, partials, variables files and the target documents are watched as well. Can be repeated.
+    #[arg(long, value_name = "PATHS", value_delimiter = ',')]
+    pub watch: Vec<PathBuf>,
+}
+
+impl From<CliOutputOptions> for OutputOptions {
+    fn from(value: CliOutputOptions) -> Self {
+        Self {
+            dry_run: value.dry_run,
+            diff_format: value.diff_format.into(),
+            backup: value.backup,
+            watch: value.watch,
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -75,6 +306,15 @@ pub struct CliWriteOptions {
     )]
     pub template_string: Option<String>,
 
+    #[command(flatten)]
+    pub templating: CliTemplateOptions,
+
+    #[command(flatten)]
+    pub output: CliOutputOptions,
+
+    #[command(flatten)]
+    pub discovery: CliDiscoveryOptions,
+
     /// Specifies the logging level for the application (default: info)
     #[arg(long, short)]
     pub log_level: Option<CliLogLevel>,
@@ -95,6 +335,82 @@ impl From<CliWriteOptions> for WriteOptions {
             template: value.template.map(|t| t.into()),
             template_file: value.template_file,
             template_string: value.template_string,
+            templating: value.templating.into(),
+            output: value.output.into(),
+            latin1_fallback: false,
+            discovery: value.discovery.into(),
+            log_level: value.log_level.map(|l| l.into()),
+            mcp_server_cmd: value.mcp_server_cmd,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliUpdateOptions {
+    /// File to update. Can be repeated and accepts glob patterns, e.g. "packages/*/README.md".
+    #[arg(short, long, required = true)]
+    pub filename: Vec<String>,
+
+    /// Select an output template from the built-in options.
+    #[arg(short, long, value_enum, conflicts_with_all = ["template_file", "template_string"])]
+    pub template: Option<CliTemplate>,
+
+    /// Path to a custom template file written in the Handlebars format.
+    #[arg(long, short = 'p',
+    conflicts_with_all = ["template", "template_string"])]
+    pub template_file: Option<PathBuf>,
+
+    /// Template content provided as a string.
+    #[arg(
+        long,
+        short = 's',
+        conflicts_with_all = ["template", "template_file"]
+    )]
+    pub template_string: Option<String>,
+
+    #[command(flatten)]
+    pub templating: CliTemplateOptions,
+
+    #[command(flatten)]
+    pub output: CliOutputOptions,
+
+    /// Read files that are not valid UTF-8 as Latin-1 (ISO-8859-1) instead of failing.
+    #[arg(long)]
+    pub latin1_fallback: bool,
+
+    /// MCP servers config file (JSON or YAML, in the `mcpServers` format), providing the servers
+    /// selected by the `server=<name>` property of render markers.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub discovery: CliDiscoveryOptions,
+
+    /// Specifies the logging level for the application (default: info)
+    #[arg(long, short)]
+    pub log_level: Option<CliLogLevel>,
+    /// Command and arguments to launch the MCP server.
+    #[arg(
+        value_name = "MCP Launch Command",
+        allow_hyphen_values = true,
+        last = true,
+        required_unless_present = "config"
+    )]
+    pub mcp_server_cmd: Vec<String>,
+}
+
+impl From<CliUpdateOptions> for UpdateOptions {
+    fn from(value: CliUpdateOptions) -> Self {
+        Self {
+            filenames: value.filename,
+            template: value.template.map(|t| t.into()),
+            template_file: value.template_file,
+            template_string: value.template_string,
+            templating: value.templating.into(),
+            output: value.output.into(),
+            latin1_fallback: value.latin1_fallback,
+            config: value.config,
+            discovery: value.discovery.into(),
             log_level: value.log_level.map(|l| l.into()),
             mcp_server_cmd: value.mcp_server_cmd,
         }
@@ -120,6 +436,12 @@ conflicts_with_all = ["template", "template_string"])]
 )]
     pub template_string: Option<String>,
 
+    #[command(flatten)]
+    pub templating: CliTemplateOptions,
+
+    #[command(flatten)]
+    pub discovery: CliDiscoveryOptions,
+
     /// Specifies the logging level for the application (default: info)
     #[arg(long, short)]
     pub log_level: Option<CliLogLevel>,
@@ -140,6 +462,145 @@ impl From<CliPrintOptions> for PrintOptions {
             template: value.template.map(|t| t.into()),
             template_file: value.template_file,
             template_string: value.template_string,
+            templating: value.templating.into(),
+            discovery: value.discovery.into(),
+            log_level: value.log_level.map(|l| l.into()),
+            mcp_server_cmd: value.mcp_server_cmd,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliSiteOptions {
+    /// Directory where the generated site will be written.
+    #[arg(short, long)]
+    pub out_dir: PathBuf,
+
+    /// Directory containing `<partial-name>.hbs` files that override built-in site page templates.
+    #[arg(long)]
+    pub template_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub discovery: CliDiscoveryOptions,
+
+    /// Specifies the logging level for the application (default: info)
+    #[arg(long, short)]
+    pub log_level: Option<CliLogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    #[arg(
+        value_name = "MCP Launch Command",
+        allow_hyphen_values = true,
+        last = true,
+        required = true
+    )]
+    pub mcp_server_cmd: Vec<String>,
+}
+
+impl From<CliSiteOptions> for SiteOptions {
+    fn from(value: CliSiteOptions) -> Self {
+        Self {
+            out_dir: value.out_dir,
+            template_dir: value.template_dir,
+            discovery: value.discovery.into(),
+            log_level: value.log_level.map(|l| l.into()),
+            mcp_server_cmd: value.mcp_server_cmd,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliBadgeOptions {
+    /// Directory where the SVG badges will be written.
+    #[arg(short, long)]
+    pub out_dir: PathBuf,
+
+    #[command(flatten)]
+    pub discovery: CliDiscoveryOptions,
+
+    /// Specifies the logging level for the application (default: info)
+    #[arg(long, short)]
+    pub log_level: Option<CliLogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    #[arg(
+        value_name = "MCP Launch Command",
+        allow_hyphen_values = true,
+        last = true,
+        required = true
+    )]
+    pub mcp_server_cmd: Vec<String>,
+}
+
+impl From<CliBadgeOptions> for BadgeOptions {
+    fn from(value: CliBadgeOptions) -> Self {
+        Self {
+            out_dir: value.out_dir,
+            discovery: value.discovery.into(),
+            log_level: value.log_level.map(|l| l.into()),
+            mcp_server_cmd: value.mcp_server_cmd,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliCallOptions {
+    /// Name of the tool to call.
+    pub tool: String,
+
+    /// Tool arguments as a JSON object, e.g. '{"query": "bug", "limit": 10}'.
+    #[arg(long)]
+    pub args: Option<String>,
+
+    /// Tool argument, coerced to the type of the parameter. Can be repeated and takes precedence over --args.
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_define)]
+    pub arg: Vec<(String, String)>,
+
+    /// Specifies the logging level for the application (default: info)
+    #[arg(long, short)]
+    pub log_level: Option<CliLogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    #[arg(
+        value_name = "MCP Launch Command",
+        allow_hyphen_values = true,
+        last = true,
+        required = true
+    )]
+    pub mcp_server_cmd: Vec<String>,
+}
+
+impl From<CliCallOptions> for CallOptions {
+    fn from(value: CliCallOptions) -> Self {
+        Self {
+            tool: value.tool,
+            args: value.args,
+            arg: value.arg,
+            log_level: value.log_level.map(|l| l.into()),
+            mcp_server_cmd: value.mcp_server_cmd,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExploreOptions {
+    /// Specifies the logging level for the application (default: info)
+    #[arg(long, short)]
+    pub log_level: Option<CliLogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    #[arg(
+        value_name = "MCP Launch Command",
+        allow_hyphen_values = true,
+        last = true,
+        required = true
+    )]
+    pub mcp_server_cmd: Vec<String>,
+}
+
+impl From<CliExploreOptions> for ExploreOptions {
+    fn from(value: CliExploreOptions) -> Self {
+        Self {
             log_level: value.log_level.map(|l| l.into()),
             mcp_server_cmd: value.mcp_server_cmd,
         }
@@ -153,8 +614,19 @@ impl From<CliDiscoveryCommand> for DiscoveryCommand {
             CliDiscoveryCommand::Create(cli_write_options) => {
                 Self::Create(cli_write_options.into())
             }
-            CliDiscoveryCommand::Update(cli_write_options) => {
-                Self::Update(cli_write_options.into())
+            CliDiscoveryCommand::Update(cli_update_options) => {
+                Self::Update(cli_update_options.into())
+            }
+            CliDiscoveryCommand::Site(cli_site_options) => Self::Site(cli_site_options.into()),
+            CliDiscoveryCommand::Badges(cli_badge_options) => {
+                Self::Badges(cli_badge_options.into())
+            }
+            CliDiscoveryCommand::Call(cli_call_options) => Self::Call(cli_call_options.into()),
+            CliDiscoveryCommand::Explore(cli_explore_options) => {
+                Self::Explore(cli_explore_options.into())
+            }
+            CliDiscoveryCommand::Templates(_) => {
+                unreachable!("templates commands are handled without launching an MCP server")
             }
         }
     }
@@ -187,6 +659,12 @@ pub struct CommandArguments {
  )]
     pub template_string: Option<String>,
 
+    #[command(flatten)]
+    pub templating: CliTemplateOptions,
+
+    #[command(flatten)]
+    pub discovery: CliDiscoveryOptions,
+
     /// Specifies the logging level for the application (default: info)
     #[arg(long, short)]
     pub log_level: Option<CliLogLevel>,
@@ -253,11 +731,9 @@ mod tests {
     fn test_file_options_match_template_custom() {
         let file_options = WriteOptions {
             filename: PathBuf::from("output.html"),
-            template: None,
             template_file: Some(PathBuf::from("templates/markdown/markdown_template.md")),
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
 
         let result = file_options.match_template();
@@ -298,6 +774,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_write_command_with_vars() {
+        let args = vec![
+            "mcp-tool",
+            "update",
+            "--filename",
+            "README.md",
+            "--define",
+            "package=my-server",
+            "--define",
+            "install=npm i a=b",
+            "--vars-file",
+            "vars.yaml",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Update(file_options)) => {
+                assert_eq!(
+                    file_options.templating.defines,
+                    vec![
+                        ("package".to_string(), "my-server".to_string()),
+                        ("install".to_string(), "npm i a=b".to_string())
+                    ]
+                );
+                assert_eq!(
+                    file_options.templating.vars_file,
+                    Some(PathBuf::from("vars.yaml"))
+                );
+            }
+            _ => panic!("Expected Update command"),
+        }
+
+        let args = vec![
+            "mcp-tool",
+            "create",
+            "-f",
+            "out.md",
+            "--define",
+            "novalue",
+            "--",
+            "mcp-server",
+        ];
+        assert!(CommandArguments::try_parse_from(args).is_err());
+    }
+
     #[test]
     fn test_update_command_with_template_file() {
         let args = vec![
@@ -316,7 +840,7 @@ mod tests {
 
         match parsed.command {
             Some(CliDiscoveryCommand::Update(file_options)) => {
-                assert_eq!(file_options.filename, PathBuf::from("output.html"));
+                assert_eq!(file_options.filename, vec!["output.html"]);
                 assert_eq!(file_options.template, None);
                 assert_eq!(
                     file_options.template_file,
@@ -331,6 +855,326 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_update_command_with_many_files() {
+        let args = vec![
+            "mcp-tool",
+            "update",
+            "--filename",
+            "README.md",
+            "-f",
+            "packages/*/README.md",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Update(file_options)) => {
+                assert_eq!(
+                    file_options.filename,
+                    vec!["README.md", "packages/*/README.md"]
+                );
+            }
+            _ => panic!("Expected Update command"),
+        }
+
+        // a filename is required
+        let args = vec!["mcp-tool", "update", "--", "mcp-server"];
+        assert!(CommandArguments::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_update_command_with_servers_config() {
+        // the launch command is optional when servers come from a config file
+        let args = vec![
+            "mcp-tool",
+            "update",
+            "-f",
+            "README.md",
+            "--config",
+            "mcp.json",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Update(file_options)) => {
+                let options: UpdateOptions = file_options.into();
+                assert_eq!(options.config, Some(PathBuf::from("mcp.json")));
+                assert!(options.mcp_server_cmd.is_empty());
+            }
+            _ => panic!("Expected Update command"),
+        }
+
+        let args = vec!["mcp-tool", "update", "-f", "README.md"];
+        assert!(CommandArguments::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_watch_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "create",
+            "-f",
+            "README.md",
+            "--watch",
+            "src,templates",
+            "--watch",
+            "Cargo.toml",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Create(file_options)) => {
+                let options: WriteOptions = file_options.into();
+                assert_eq!(
+                    options.output.watch,
+                    vec![
+                        PathBuf::from("src"),
+                        PathBuf::from("templates"),
+                        PathBuf::from("Cargo.toml")
+                    ]
+                );
+                // the target document is watched along with the given paths
+                let watched = DiscoveryCommand::Create(options).watched_paths().unwrap();
+                assert_eq!(watched.last(), Some(&PathBuf::from("README.md")));
+            }
+            _ => panic!("Expected Create command"),
+        }
+    }
+
+    #[test]
+    fn test_dry_run_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "update",
+            "-f",
+            "README.md",
+            "--dry-run",
+            "--diff-format",
+            "json",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+        let command: DiscoveryCommand = parsed.command.unwrap().into();
+        match &command {
+            DiscoveryCommand::Update(update_options) => {
+                assert!(update_options.output.dry_run);
+                assert_eq!(update_options.output.diff_format, DiffFormat::Json);
+            }
+            _ => panic!("Expected Update command"),
+        }
+        assert!(command.json_output());
+
+        let args = vec![
+            "mcp-tool",
+            "create",
+            "-f",
+            "out.md",
+            "--dry-run",
+            "--",
+            "mcp-server",
+        ];
+        let command: DiscoveryCommand = parse_args(args).command.unwrap().into();
+        match &command {
+            DiscoveryCommand::Create(create_options) => {
+                assert!(create_options.output.dry_run);
+                assert_eq!(create_options.output.diff_format, DiffFormat::Unified);
+            }
+            _ => panic!("Expected Create command"),
+        }
+        assert!(!command.json_output());
+
+        // a diff format is only meaningful for a dry run
+        let args = vec![
+            "mcp-tool",
+            "create",
+            "-f",
+            "out.md",
+            "--diff-format",
+            "json",
+            "--",
+            "mcp-server",
+        ];
+        assert!(CommandArguments::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_site_command_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "site",
+            "--out-dir",
+            "docs/site",
+            "--template-dir",
+            "site-templates",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Site(site_options)) => {
+                assert_eq!(site_options.out_dir, PathBuf::from("docs/site"));
+                assert_eq!(
+                    site_options.template_dir,
+                    Some(PathBuf::from("site-templates"))
+                );
+                assert_eq!(site_options.mcp_server_cmd, vec!["mcp-server"]);
+            }
+            _ => panic!("Expected Site command"),
+        }
+    }
+
+    #[test]
+    fn test_badges_command_parsing() {
+        let args = vec!["mcp-tool", "badges", "-o", "badges", "--", "mcp-server"];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Badges(badge_options)) => {
+                assert_eq!(badge_options.out_dir, PathBuf::from("badges"));
+                assert_eq!(badge_options.mcp_server_cmd, vec!["mcp-server"]);
+            }
+            _ => panic!("Expected Badges command"),
+        }
+    }
+
+    #[test]
+    fn test_call_command_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "call",
+            "search_issues",
+            "--args",
+            r#"{"query": "bug"}"#,
+            "--arg",
+            "limit=10",
+            "--arg",
+            "labels=ui,crash",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Call(call_options)) => {
+                assert_eq!(call_options.tool, "search_issues");
+                assert_eq!(call_options.args, Some(r#"{"query": "bug"}"#.to_string()));
+                assert_eq!(
+                    call_options.arg,
+                    vec![
+                        ("limit".to_string(), "10".to_string()),
+                        ("labels".to_string(), "ui,crash".to_string())
+                    ]
+                );
+                assert_eq!(call_options.mcp_server_cmd, vec!["mcp-server"]);
+            }
+            _ => panic!("Expected Call command"),
+        }
+    }
+
+    #[test]
+    fn test_explore_command_parsing() {
+        let args = vec![
+            "mcp-discovery",
+            "explore",
+            "-l",
+            "debug",
+            "--",
+            "npx",
+            "-y",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Explore(explore_options)) => {
+                assert!(matches!(
+                    explore_options.log_level,
+                    Some(CliLogLevel::debug)
+                ));
+                assert_eq!(
+                    explore_options.mcp_server_cmd,
+                    vec!["npx", "-y", "mcp-server"]
+                );
+            }
+            _ => panic!("Expected Explore command"),
+        }
+    }
+
+    #[test]
+    fn test_templates_export_command_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "templates",
+            "export",
+            "md-plain",
+            "--out",
+            "tpl",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Templates(CliTemplatesCommand::Export(export_options))) => {
+                assert_eq!(export_options.template, CliTemplate::MdPlain);
+                assert_eq!(export_options.out, PathBuf::from("tpl"));
+                assert!(!export_options.force);
+            }
+            _ => panic!("Expected Templates Export command"),
+        }
+    }
+
+    #[test]
+    fn test_templates_validate_command_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "templates",
+            "validate",
+            "custom.hbs",
+            "--define",
+            "pkg=my-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Templates(CliTemplatesCommand::Validate(
+                validate_options,
+            ))) => {
+                let validate_options: ValidateOptions = validate_options.into();
+                assert_eq!(validate_options.template_file, PathBuf::from("custom.hbs"));
+                assert_eq!(validate_options.templating.partials_dir, None);
+                assert_eq!(
+                    validate_options.templating.defines,
+                    vec![("pkg".to_string(), "my-server".to_string())]
+                );
+                // templates are validated in strict mode unless opted out
+                assert!(validate_options.templating.strict);
+            }
+            _ => panic!("Expected Templates Validate command"),
+        }
+
+        let args = vec![
+            "mcp-tool",
+            "templates",
+            "validate",
+            "custom.hbs",
+            "--no-strict",
+        ];
+        match parse_args(args).command {
+            Some(CliDiscoveryCommand::Templates(CliTemplatesCommand::Validate(
+                validate_options,
+            ))) => {
+                let validate_options: ValidateOptions = validate_options.into();
+                assert!(!validate_options.templating.strict);
+            }
+            _ => panic!("Expected Templates Validate command"),
+        }
+    }
+
     #[test]
     fn test_print_command_with_json() {
         let args = vec!["mcp-tool", "print", "--", "mcp-server", "--verbose"];
@@ -347,6 +1191,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_read_resources_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "print",
+            "--read-resources",
+            "--resource-include",
+            "file:///docs/*",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Print(print_options)) => {
+                let discovery: DiscoveryOptions = print_options.discovery.into();
+                assert!(discovery.read_resources);
+                assert_eq!(discovery.resource_size_limit, 1024);
+                assert_eq!(
+                    discovery.resource_include,
+                    Some("file:///docs/*".to_string())
+                );
+                assert_eq!(discovery.resource_exclude, None);
+            }
+            _ => panic!("Expected Print command"),
+        }
+
+        // resource options have no effect without --read-resources
+        let args = vec![
+            "mcp-tool",
+            "--resource-size-limit",
+            "10",
+            "--",
+            "mcp-server",
+        ];
+        assert!(CommandArguments::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_prompt_samples_parsing() {
+        let args = vec![
+            "mcp-tool",
+            "update",
+            "--filename",
+            "README.md",
+            "--prompt-samples",
+            "--prompt-args",
+            "prompts.yaml",
+            "--",
+            "mcp-server",
+        ];
+        let parsed = parse_args(args);
+
+        match parsed.command {
+            Some(CliDiscoveryCommand::Update(update_options)) => {
+                let discovery: DiscoveryOptions = update_options.discovery.into();
+                assert!(discovery.prompt_samples);
+                assert_eq!(
+                    discovery.prompt_args_file,
+                    Some(PathBuf::from("prompts.yaml"))
+                );
+                assert!(!discovery.read_resources);
+            }
+            _ => panic!("Expected Update command"),
+        }
+    }
+
     #[test]
     fn test_mcp_launch_command_retrieval() {
         let args = vec![
@@ -372,10 +1283,8 @@ mod tests {
         let file_options = WriteOptions {
             filename: PathBuf::from("output.md"),
             template: Some(Template::Md),
-            template_file: None,
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
 
         let result = file_options.match_template();
@@ -0,0 +1,55 @@
+//! Module for probing the completion values of prompt arguments and resource template variables.
+
+/// Operators that may prefix the variables of a URI template expression, as defined by RFC 6570.
+const URI_TEMPLATE_OPERATORS: [char; 7] = ['+', '#', '.', '/', ';', '?', '&'];
+
+/// Returns the names of the variables of a URI template, in order of appearance and without duplicates.
+/// Expressions may hold several variables with operators and modifiers, e.g. `{/path*}` or `{?q,lang:2}`.
+pub fn uri_template_variables(uri_template: &str) -> Vec<String> {
+    let mut variables: Vec<String> = vec![];
+    let mut rest = uri_template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let expression = rest[start + 1..start + end].trim_start_matches(URI_TEMPLATE_OPERATORS);
+
+        for variable in expression.split(',') {
+            let name = variable
+                .split(':')
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches('*')
+                .trim();
+            if !name.is_empty() && !variables.iter().any(|v| v == name) {
+                variables.push(name.to_string());
+            }
+        }
+        rest = &rest[start + end + 1..];
+    }
+    variables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_template_variables() {
+        assert_eq!(
+            uri_template_variables("repos://{owner}/{repo}/issues/{id}"),
+            vec!["owner", "repo", "id"]
+        );
+        assert_eq!(
+            uri_template_variables("file://{+path}{?query,lang:2}{#section}"),
+            vec!["path", "query", "lang", "section"]
+        );
+        assert_eq!(
+            uri_template_variables("docs://{/segments*}/{segments}"),
+            vec!["segments"]
+        );
+        assert!(uri_template_variables("file:///readme.md").is_empty());
+        assert!(uri_template_variables("broken://{id").is_empty());
+    }
+}
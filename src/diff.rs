@@ -0,0 +1,146 @@
+//! Module for previewing the changes made by `create` and `update`, without writing any file.
+
+use std::path::Path;
+
+use colored::Colorize;
+use serde_json::{json, Value};
+use similar::{ChangeTag, TextDiff};
+
+use crate::types::DiffFormat;
+
+/// Number of unchanged lines shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Returns a colored unified diff between the current and new content of a file,
+/// or an empty string if the content is unchanged.
+pub fn unified_diff(file: &Path, current: &str, new: &str) -> String {
+    let file_name = file.to_string_lossy();
+    let diff = TextDiff::from_lines(current, new);
+
+    let mut lines: Vec<String> = vec![];
+    for hunk in diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .iter_hunks()
+    {
+        if lines.is_empty() {
+            lines.push(format!("--- a/{file_name}").bold().to_string());
+            lines.push(format!("+++ b/{file_name}").bold().to_string());
+        }
+        for line in hunk.to_string().lines() {
+            let line = match line.chars().next() {
+                Some('@') => line.cyan().to_string(),
+                Some('-') => line.red().to_string(),
+                Some('+') => line.green().to_string(),
+                _ => line.to_string(),
+            };
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+/// Returns the changes to a file as a JSON object, for use by other tools.
+///
+/// Hunks hold 1-based `old_start`/`new_start` line numbers, line counts, and their lines
+/// prefixed with `' '`, `'-'` or `'+'` as in a unified diff.
+/// A file that does not exist yet has `exists` set to false and a single hunk adding every line.
+pub fn json_diff(file: &Path, current: Option<&str>, new: &str) -> Value {
+    let diff = TextDiff::from_lines(current.unwrap_or_default(), new);
+
+    let hunks: Vec<Value> = diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .iter_hunks()
+        .filter_map(|hunk| {
+            let (first, last) = (hunk.ops().first()?, hunk.ops().last()?);
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+            let lines: Vec<String> = hunk
+                .iter_changes()
+                .map(|change| {
+                    let sign = match change.tag() {
+                        ChangeTag::Equal => ' ',
+                        ChangeTag::Delete => '-',
+                        ChangeTag::Insert => '+',
+                    };
+                    format!("{sign}{}", change.value().trim_end_matches(['\r', '\n']))
+                })
+                .collect();
+            Some(json!({
+                "old_start": old_range.start + 1,
+                "old_lines": old_range.len(),
+                "new_start": new_range.start + 1,
+                "new_lines": new_range.len(),
+                "lines": lines,
+            }))
+        })
+        .collect();
+
+    json!({
+        "file": file.to_string_lossy(),
+        "exists": current.is_some(),
+        "changed": current != Some(new),
+        "hunks": hunks,
+    })
+}
+
+/// Prints the changes that writing `new` to a file would make, in the requested format.
+/// With the unified format, a new file is printed in full and an unchanged file prints nothing.
+pub fn print_diff(file: &Path, current: Option<&str>, new: &str, diff_format: &DiffFormat) {
+    match (diff_format, current) {
+        (DiffFormat::Json, _) => println!("{}", json_diff(file, current, new)),
+        (DiffFormat::Unified, None) => {
+            println!("{} {}", "New file:".bold(), file.to_string_lossy());
+            println!("{new}");
+        }
+        (DiffFormat::Unified, Some(current)) => {
+            let diff = unified_diff(file, current, new);
+            if !diff.is_empty() {
+                println!("{diff}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let file = Path::new("README.md");
+
+        let diff = strip_ansi_escapes::strip_str(unified_diff(file, "a\nb\nc\n", "a\nB\nc\n"));
+        assert_eq!(
+            diff,
+            "--- a/README.md\n+++ b/README.md\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c"
+        );
+
+        assert!(unified_diff(file, "a\n", "a\n").is_empty());
+    }
+
+    #[test]
+    fn test_json_diff() {
+        let file = Path::new("README.md");
+
+        let diff = json_diff(file, Some("a\nb\nc\n"), "a\nB\nc\n");
+        assert_eq!(diff["exists"], json!(true));
+        assert_eq!(diff["changed"], json!(true));
+        assert_eq!(
+            diff["hunks"],
+            json!([{
+                "old_start": 1, "old_lines": 3, "new_start": 1, "new_lines": 3,
+                "lines": [" a", "-b", "+B", " c"]
+            }])
+        );
+
+        let diff = json_diff(file, None, "a\nb\n");
+        assert_eq!(diff["exists"], json!(false));
+        assert_eq!(diff["hunks"][0]["lines"], json!(["+a", "+b"]));
+
+        let diff = json_diff(file, Some("a\n"), "a\n");
+        assert_eq!(diff["changed"], json!(false));
+        assert_eq!(diff["hunks"], json!([]));
+    }
+}
@@ -0,0 +1,169 @@
+//! Module for reading and writing text files in the encodings supported by `update`.
+
+use std::path::Path;
+
+use crate::error::{DiscoveryError, DiscoveryResult};
+
+/// Encoding of a text file, detected when the file is read and used again to write it back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextEncoding {
+    Utf8,
+    /// UTF-16 little endian, identified by its byte order mark.
+    Utf16Le,
+    /// UTF-16 big endian, identified by its byte order mark.
+    Utf16Be,
+    /// ISO-8859-1, only used as a fallback for files that are not valid UTF-8.
+    Latin1,
+}
+
+impl TextEncoding {
+    /// Decodes the content of a file, detecting UTF-16 from its byte order mark and falling back
+    /// to Latin-1 for content that is not valid UTF-8 when `latin1_fallback` is set.
+    ///
+    /// Byte order marks are kept in the decoded text as `\u{feff}`, so they are written back as-is.
+    pub fn decode(bytes: &[u8], latin1_fallback: bool) -> Result<(String, Self), String> {
+        let (encoding, from_bytes): (Self, fn([u8; 2]) -> u16) = match bytes {
+            [0xFF, 0xFE, ..] => (Self::Utf16Le, u16::from_le_bytes),
+            [0xFE, 0xFF, ..] => (Self::Utf16Be, u16::from_be_bytes),
+            _ => {
+                return match std::str::from_utf8(bytes) {
+                    Ok(text) => Ok((text.to_string(), Self::Utf8)),
+                    Err(_) if latin1_fallback => {
+                        Ok((bytes.iter().map(|b| char::from(*b)).collect(), Self::Latin1))
+                    }
+                    Err(error) => Err(format!(
+                        "content is not valid UTF-8 ({error}). Use --latin1-fallback to read it as Latin-1."
+                    )),
+                }
+            }
+        };
+
+        if bytes.len() % 2 != 0 {
+            return Err(format!(
+                "content is not valid {encoding}, it has an odd number of bytes."
+            ));
+        }
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        let text = String::from_utf16(&units)
+            .map_err(|error| format!("content is not valid {encoding} ({error})."))?;
+        Ok((text, encoding))
+    }
+
+    /// Encodes text, failing for characters that cannot be represented in Latin-1.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Utf8 => Ok(text.as_bytes().to_vec()),
+            Self::Utf16Le => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Self::Utf16Be => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            Self::Latin1 => text
+                .chars()
+                .map(|c| {
+                    u8::try_from(c).map_err(|_| {
+                        format!("character '{c}' cannot be encoded in Latin-1. Use a template without it.")
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Latin1 => "Latin-1",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Reads a text file, returning its decoded content along with the detected encoding.
+pub fn read_text_file(
+    path: &Path,
+    latin1_fallback: bool,
+) -> DiscoveryResult<(String, TextEncoding)> {
+    let bytes = std::fs::read(path)
+        .map_err(|error| DiscoveryError::ReadFile(path.to_string_lossy().into_owned(), error))?;
+
+    TextEncoding::decode(&bytes, latin1_fallback).map_err(|reason| {
+        DiscoveryError::InvalidEncoding(path.to_string_lossy().into_owned(), reason)
+    })
+}
+
+/// Encodes text to be written to a file, in the encoding the file was read with.
+pub fn encode_text_file(
+    path: &Path,
+    text: &str,
+    encoding: TextEncoding,
+) -> DiscoveryResult<Vec<u8>> {
+    encoding.encode(text).map_err(|reason| {
+        DiscoveryError::InvalidEncoding(path.to_string_lossy().into_owned(), reason)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_encode_round_trip() {
+        let text = "\u{feff}# Café ✔\r\n";
+        for encoding in [
+            TextEncoding::Utf8,
+            TextEncoding::Utf16Le,
+            TextEncoding::Utf16Be,
+        ] {
+            let bytes = encoding.encode(text).unwrap();
+            let (decoded, detected) = TextEncoding::decode(&bytes, false).unwrap();
+            assert_eq!(detected, encoding);
+            assert_eq!(decoded, text);
+        }
+
+        assert_eq!(
+            TextEncoding::Utf16Le.encode("\u{feff}a").unwrap(),
+            vec![0xFF, 0xFE, b'a', 0]
+        );
+    }
+
+    #[test]
+    fn test_decode_latin1_fallback() {
+        let bytes = b"caf\xe9\n";
+        let error = TextEncoding::decode(bytes, false).unwrap_err();
+        assert!(error.contains("--latin1-fallback"), "{error}");
+
+        let (decoded, encoding) = TextEncoding::decode(bytes, true).unwrap();
+        assert_eq!(encoding, TextEncoding::Latin1);
+        assert_eq!(decoded, "café\n");
+        assert_eq!(encoding.encode(&decoded).unwrap(), bytes.to_vec());
+
+        // valid UTF-8 is never read as Latin-1
+        assert_eq!(
+            TextEncoding::decode("café".as_bytes(), true).unwrap().1,
+            TextEncoding::Utf8
+        );
+
+        assert!(TextEncoding::Latin1.encode("✔").is_err());
+    }
+
+    #[test]
+    fn test_read_text_file_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let missing = dir.path().join("missing.md");
+        assert!(matches!(
+            read_text_file(&missing, false),
+            Err(DiscoveryError::ReadFile(_, _))
+        ));
+
+        let odd = dir.path().join("odd.md");
+        std::fs::write(&odd, [0xFF, 0xFE, b'a']).unwrap();
+        assert!(matches!(
+            read_text_file(&odd, false),
+            Err(DiscoveryError::InvalidEncoding(_, _))
+        ));
+    }
+}
@@ -13,6 +13,28 @@ pub enum DiscoveryError {
     InvalidSchema(String),
     #[error("{0}")]
     ParseTemplate(String),
+    #[error("Failed to load template file '{0}': {1}")]
+    LoadTemplate(String, std::io::Error),
+    #[error("Failed to read '{0}': {1}")]
+    ReadFile(String, std::io::Error),
+    #[error("Encoding error in '{0}': {1}")]
+    InvalidEncoding(String, String),
+    #[error("{0}")]
+    InvalidVars(String),
+    #[error("{0}")]
+    InvalidPattern(String),
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("{0}")]
+    InvalidArguments(String),
+    #[error("Tool '{0}' was not found. Available tools: {1}")]
+    ToolNotFound(String, String),
+    #[error("Tool '{0}' returned an error.")]
+    ToolCallFailed(String),
+    #[error("Failed to discover server '{0}': {1}")]
+    ServerDiscovery(String, Box<DiscoveryError>),
+    #[error("Failed to update {0} of {1} files.")]
+    UpdateFailed(usize, usize),
     #[error(
         "Server details are not available. please ensure the discover() method is called first."
     )]
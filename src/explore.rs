@@ -0,0 +1,651 @@
+//! Module for the interactive explorer, browsing and using the capabilities of a running MCP server
+//! in one session.
+
+use std::{
+    collections::HashMap,
+    io::{stdout, Write},
+    sync::Arc,
+};
+
+use colored::Colorize;
+use rust_mcp_sdk::{
+    mcp_client::ClientRuntime,
+    schema::{
+        CallToolRequestParams, GetPromptRequestParams, ReadResourceContent,
+        ReadResourceRequestParams, Role,
+    },
+    McpClient,
+};
+use serde_json::{Map, Value};
+use tokio::io::{stdin, AsyncBufReadExt, BufReader, Lines, Stdin};
+
+use crate::{
+    call::{
+        build_arguments, coerce_arg, format_blob_resource, format_call_result,
+        format_content_block, format_text_resource, validate_arguments,
+    },
+    error::{DiscoveryError, DiscoveryResult},
+    std_output::{print_list, print_summary},
+    types::McpToolSParams,
+    McpServerInfo, McpToolMeta, ParamTypes,
+};
+
+const HELP: &str = "Commands:
+  info                          Show the server summary
+  tools [query]                 List tools, optionally matching a query
+  prompts [query]               List prompts, optionally matching a query
+  resources [query]             List resources, optionally matching a query
+  templates [query]             List resource templates, optionally matching a query
+  search <query>                Search tools, prompts, resources and resource templates
+  schema <tool>                 Show the input schema of a tool as a tree
+  call <tool> [json]            Call a tool, prompting for arguments when no JSON object is given
+  read <uri>                    Read a resource
+  prompt <name> [key=value...]  Get a prompt, prompting for missing required arguments
+  help                          Show this help
+  quit                          End the session";
+
+/// Kinds of items provided by an MCP server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Tool,
+    Prompt,
+    Resource,
+    ResourceTemplate,
+}
+
+impl std::fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ItemKind::Tool => "tool",
+            ItemKind::Prompt => "prompt",
+            ItemKind::Resource => "resource",
+            ItemKind::ResourceTemplate => "resource template",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A command entered in the explorer.
+#[derive(Debug, PartialEq)]
+pub enum ExploreCommand {
+    Info,
+    List(ItemKind, Option<String>),
+    Search(String),
+    Schema(String),
+    Call(String, Option<String>),
+    Read(String),
+    Prompt(String, Vec<(String, String)>),
+    Help,
+    Quit,
+    Empty,
+    Invalid(String),
+}
+
+impl ExploreCommand {
+    /// Parses a line entered in the explorer.
+    pub fn parse(line: &str) -> Self {
+        let line = line.trim();
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let argument = (!rest.is_empty()).then(|| rest.to_string());
+
+        let required = |usage: &str, command: fn(String) -> Self| match &argument {
+            Some(argument) => command(argument.to_owned()),
+            None => Self::Invalid(format!("Usage: {usage}")),
+        };
+
+        match command {
+            "" => Self::Empty,
+            "info" => Self::Info,
+            "tools" => Self::List(ItemKind::Tool, argument),
+            "prompts" => Self::List(ItemKind::Prompt, argument),
+            "resources" => Self::List(ItemKind::Resource, argument),
+            "templates" => Self::List(ItemKind::ResourceTemplate, argument),
+            "search" => required("search <query>", Self::Search),
+            "schema" => required("schema <tool>", Self::Schema),
+            "read" => required("read <uri>", Self::Read),
+            "call" => match rest.split_once(char::is_whitespace) {
+                Some((tool, json)) => Self::Call(tool.to_string(), Some(json.trim().to_string())),
+                None if !rest.is_empty() => Self::Call(rest.to_string(), None),
+                None => Self::Invalid("Usage: call <tool> [json]".to_string()),
+            },
+            "prompt" => {
+                let mut words = rest.split_whitespace();
+                let Some(name) = words.next() else {
+                    return Self::Invalid("Usage: prompt <name> [key=value...]".to_string());
+                };
+                let arguments: Option<Vec<(String, String)>> = words
+                    .map(|word| {
+                        let (key, value) = word.split_once('=')?;
+                        Some((key.to_string(), value.to_string()))
+                    })
+                    .collect();
+                match arguments {
+                    Some(arguments) => Self::Prompt(name.to_string(), arguments),
+                    None => Self::Invalid("Prompt arguments must be key=value pairs".to_string()),
+                }
+            }
+            "help" | "?" => Self::Help,
+            "quit" | "exit" => Self::Quit,
+            _ => Self::Invalid(format!(
+                "Unknown command '{command}'. Type 'help' for the list of commands."
+            )),
+        }
+    }
+}
+
+/// Returns the items of a server matching a case-insensitive query, as `(kind, name, description)`.
+/// The query is matched against names, titles, descriptions and resource URIs.
+pub fn search_items(
+    server_info: &McpServerInfo,
+    kind: Option<ItemKind>,
+    query: Option<&str>,
+) -> Vec<(ItemKind, String, String)> {
+    let mut items: Vec<(ItemKind, String, String, Vec<&str>)> = vec![];
+    for tool in server_info.tools.iter().flatten() {
+        items.push((
+            ItemKind::Tool,
+            tool.name.to_owned(),
+            tool.description.to_owned().unwrap_or_default(),
+            vec![tool.title.as_deref().unwrap_or_default()],
+        ));
+    }
+    for prompt in server_info.prompts.iter().flatten() {
+        items.push((
+            ItemKind::Prompt,
+            prompt.name.to_owned(),
+            prompt.description.to_owned().unwrap_or_default(),
+            vec![prompt.title.as_deref().unwrap_or_default()],
+        ));
+    }
+    for resource in server_info.resources.iter().flatten() {
+        items.push((
+            ItemKind::Resource,
+            resource.name.to_owned(),
+            resource.description.to_owned().unwrap_or_default(),
+            vec![resource.title.as_deref().unwrap_or_default(), &resource.uri],
+        ));
+    }
+    for template in server_info.resource_templates.iter().flatten() {
+        items.push((
+            ItemKind::ResourceTemplate,
+            template.name.to_owned(),
+            template.description.to_owned().unwrap_or_default(),
+            vec![
+                template.title.as_deref().unwrap_or_default(),
+                &template.uri_template,
+            ],
+        ));
+    }
+
+    let query = query.map(str::to_lowercase);
+    items
+        .into_iter()
+        .filter(|(item_kind, ..)| kind.map_or(true, |kind| kind == *item_kind))
+        .filter(|(_, name, description, others)| {
+            query.as_ref().map_or(true, |query| {
+                [name.as_str(), description.as_str()]
+                    .iter()
+                    .chain(others.iter())
+                    .any(|text| text.to_lowercase().contains(query))
+            })
+        })
+        .map(|(kind, name, description, _)| (kind, name, description))
+        .collect()
+}
+
+/// Returns a short label for a parameter type, detailed by its children in the schema tree.
+fn type_label(param_type: &ParamTypes) -> String {
+    match param_type {
+        ParamTypes::Primitive(type_name) => type_name.to_owned(),
+        ParamTypes::Object(_) => "object".to_string(),
+        ParamTypes::Array(_) => "array".to_string(),
+        ParamTypes::Anyof(_) => "anyOf".to_string(),
+        ParamTypes::OneOf(_) => "oneOf".to_string(),
+        ParamTypes::AllOf(_) => "allOf".to_string(),
+        ParamTypes::EnumValues(_) => "enum".to_string(),
+    }
+}
+
+/// Returns the child nodes of a parameter type in the schema tree, with their label.
+fn child_nodes(param_type: &ParamTypes) -> Vec<(String, Option<&ParamTypes>)> {
+    match param_type {
+        ParamTypes::Primitive(_) => vec![],
+        ParamTypes::Object(params) => params
+            .iter()
+            .map(|param| {
+                let required = if param.required { " (required)" } else { "" };
+                (
+                    format!(
+                        "{}: {}{required}",
+                        param.param_name,
+                        type_label(&param.param_type)
+                    ),
+                    Some(&param.param_type),
+                )
+            })
+            .collect(),
+        ParamTypes::Array(items) => items
+            .iter()
+            .map(|item| (format!("items: {}", type_label(item)), Some(item)))
+            .collect(),
+        ParamTypes::Anyof(variants) | ParamTypes::OneOf(variants) | ParamTypes::AllOf(variants) => {
+            variants
+                .iter()
+                .map(|variant| (type_label(variant), Some(variant)))
+                .collect()
+        }
+        ParamTypes::EnumValues(values) => values
+            .iter()
+            .map(|value| (value.to_string(), None))
+            .collect(),
+    }
+}
+
+fn push_tree_lines(param_type: &ParamTypes, prefix: &str, lines: &mut Vec<String>) {
+    let children = child_nodes(param_type);
+    let count = children.len();
+    for (index, (label, child)) in children.into_iter().enumerate() {
+        let is_last = index + 1 == count;
+        lines.push(format!(
+            "{prefix}{}{label}",
+            if is_last { "└── " } else { "├── " }
+        ));
+        if let Some(child) = child {
+            let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+            push_tree_lines(child, &child_prefix, lines);
+        }
+    }
+}
+
+/// Returns the parameters of a tool, marked as required according to the input schema of the tool.
+fn input_params(tool: &McpToolMeta) -> Vec<McpToolSParams> {
+    tool.params
+        .iter()
+        .map(|param| McpToolSParams {
+            required: tool.input_schema.required.contains(&param.param_name),
+            ..param.to_owned()
+        })
+        .collect()
+}
+
+/// Renders the parameters of a tool as a tree, nesting objects, array items, variants and enum values.
+pub fn schema_tree(tool: &McpToolMeta) -> String {
+    let mut lines = vec![tool.name.to_owned()];
+    push_tree_lines(&ParamTypes::Object(input_params(tool)), "", &mut lines);
+    lines.join("\n")
+}
+
+/// An interactive session with a running MCP server, reading commands from stdin.
+pub struct ExploreSession {
+    client: Arc<ClientRuntime>,
+    server_info: McpServerInfo,
+    input: Lines<BufReader<Stdin>>,
+}
+
+impl ExploreSession {
+    pub fn new(client: Arc<ClientRuntime>, server_info: McpServerInfo) -> Self {
+        Self {
+            client,
+            server_info,
+            input: BufReader::new(stdin()).lines(),
+        }
+    }
+
+    /// Reads commands until `quit` or the end of the input. Command errors are reported
+    /// without ending the session.
+    pub async fn run(&mut self) -> DiscoveryResult<()> {
+        println!(
+            "Exploring {} v{}. Type 'help' for the list of commands.",
+            self.server_info.name.bold(),
+            self.server_info.version
+        );
+
+        while let Some(line) = self
+            .read_line(&format!("{} ", "mcp>".cyan().bold()))
+            .await?
+        {
+            match ExploreCommand::parse(&line) {
+                ExploreCommand::Quit => break,
+                command => {
+                    if let Err(error) = self.execute(command).await {
+                        eprintln!("{} {error}", "Error:".red().bold());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints a prompt and reads a line, returning `None` at the end of the input.
+    async fn read_line(&mut self, prompt: &str) -> DiscoveryResult<Option<String>> {
+        print!("{prompt}");
+        stdout().flush()?;
+        Ok(self.input.next_line().await?)
+    }
+
+    async fn execute(&mut self, command: ExploreCommand) -> DiscoveryResult<()> {
+        match command {
+            ExploreCommand::Info => {
+                print_summary(&mut stdout(), &self.server_info)?;
+            }
+            ExploreCommand::List(kind, query) => {
+                self.print_items(Some(kind), query.as_deref())?;
+            }
+            ExploreCommand::Search(query) => {
+                self.print_items(None, Some(&query))?;
+            }
+            ExploreCommand::Schema(name) => {
+                let tool = self.tool(&name)?;
+                println!("{}", schema_tree(tool));
+            }
+            ExploreCommand::Call(name, json) => {
+                self.call_tool(&name, json.as_deref()).await?;
+            }
+            ExploreCommand::Read(uri) => {
+                self.read_resource(&uri).await?;
+            }
+            ExploreCommand::Prompt(name, arguments) => {
+                self.get_prompt(&name, arguments).await?;
+            }
+            ExploreCommand::Help => println!("{HELP}"),
+            ExploreCommand::Invalid(message) => eprintln!("{message}"),
+            ExploreCommand::Quit | ExploreCommand::Empty => {}
+        }
+        Ok(())
+    }
+
+    fn print_items(&self, kind: Option<ItemKind>, query: Option<&str>) -> DiscoveryResult<()> {
+        let items = search_items(&self.server_info, kind, query);
+        if items.is_empty() {
+            println!("{}", "No matching items.".dimmed());
+            return Ok(());
+        }
+        print_list(
+            stdout(),
+            items
+                .into_iter()
+                .map(|(item_kind, name, description)| {
+                    let name = match kind {
+                        Some(_) => name,
+                        None => format!("{item_kind} {name}"),
+                    };
+                    let summary = description.lines().next().unwrap_or_default().to_string();
+                    (name, summary)
+                })
+                .collect(),
+        )?;
+        Ok(())
+    }
+
+    fn tool(&self, name: &str) -> DiscoveryResult<&McpToolMeta> {
+        let tools = self.server_info.tools.as_deref().unwrap_or_default();
+        tools.iter().find(|tool| tool.name == name).ok_or_else(|| {
+            DiscoveryError::ToolNotFound(
+                name.to_string(),
+                tools
+                    .iter()
+                    .map(|tool| tool.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })
+    }
+
+    /// Prompts for the value of each parameter of a tool, coerced to the type of the parameter.
+    /// Returns `None` when the input ends before all values are entered.
+    async fn prompt_tool_arguments(
+        &mut self,
+        params: &[McpToolSParams],
+    ) -> DiscoveryResult<Option<Map<String, Value>>> {
+        println!(
+            "{}",
+            "Enter a value for each parameter, leaving optional parameters empty to skip them."
+                .dimmed()
+        );
+        let mut arguments = Map::new();
+        for param in params.iter() {
+            loop {
+                let prompt = format!(
+                    "{} ({}{}): ",
+                    param.param_name.cyan(),
+                    param.param_type,
+                    if param.required { ", required" } else { "" }
+                );
+                let Some(value) = self.read_line(&prompt).await? else {
+                    return Ok(None);
+                };
+                if value.is_empty() {
+                    if param.required {
+                        eprintln!("'{}' is required.", param.param_name);
+                        continue;
+                    }
+                    break;
+                }
+                match coerce_arg(&value, &param.param_type) {
+                    Ok(value) => {
+                        arguments.insert(param.param_name.to_owned(), value);
+                        break;
+                    }
+                    Err(reason) => eprintln!("{reason}"),
+                }
+            }
+        }
+        Ok(Some(arguments))
+    }
+
+    async fn call_tool(&mut self, name: &str, json: Option<&str>) -> DiscoveryResult<()> {
+        let tool = self.tool(name)?.to_owned();
+
+        let arguments = match json {
+            Some(json) => build_arguments(&tool, Some(json), &[])?,
+            None => match self.prompt_tool_arguments(&input_params(&tool)).await? {
+                Some(arguments) => arguments,
+                None => return Ok(()),
+            },
+        };
+
+        let problems = validate_arguments(&tool, &arguments);
+        if !problems.is_empty() {
+            return Err(DiscoveryError::InvalidArguments(format!(
+                "Invalid arguments for tool '{}':\n  {}",
+                tool.name,
+                problems.join("\n  ")
+            )));
+        }
+
+        let result = self
+            .client
+            .request_tool_call(CallToolRequestParams {
+                name: tool.name.to_owned(),
+                arguments: Some(arguments),
+                meta: None,
+                task: None,
+            })
+            .await?;
+        println!("{}", format_call_result(&result));
+        Ok(())
+    }
+
+    async fn read_resource(&mut self, uri: &str) -> DiscoveryResult<()> {
+        let result = self
+            .client
+            .request_resource_read(ReadResourceRequestParams {
+                uri: uri.to_string(),
+                meta: None,
+            })
+            .await?;
+
+        for content in result.contents.iter() {
+            let lines = match content {
+                ReadResourceContent::TextResourceContents(resource) => {
+                    format_text_resource(resource)
+                }
+                ReadResourceContent::BlobResourceContents(resource) => {
+                    format_blob_resource(resource)
+                }
+            };
+            println!("{}", lines.join("\n"));
+        }
+        Ok(())
+    }
+
+    async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: Vec<(String, String)>,
+    ) -> DiscoveryResult<()> {
+        let prompts = self.server_info.prompts.as_deref().unwrap_or_default();
+        let prompt = prompts
+            .iter()
+            .find(|prompt| prompt.name == name)
+            .ok_or_else(|| {
+                DiscoveryError::InvalidArguments(format!(
+                    "Prompt '{name}' was not found. Available prompts: {}",
+                    prompts
+                        .iter()
+                        .map(|prompt| prompt.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?
+            .to_owned();
+
+        let mut arguments: HashMap<String, String> = arguments.into_iter().collect();
+        for argument in prompt.arguments.iter() {
+            while argument.required == Some(true) && !arguments.contains_key(&argument.name) {
+                let Some(value) = self
+                    .read_line(&format!("{} (required): ", argument.name.cyan()))
+                    .await?
+                else {
+                    return Ok(());
+                };
+                if !value.is_empty() {
+                    arguments.insert(argument.name.to_owned(), value);
+                }
+            }
+        }
+
+        let result = self
+            .client
+            .request_prompt(GetPromptRequestParams {
+                name: prompt.name.to_owned(),
+                arguments: Some(arguments),
+                meta: None,
+            })
+            .await?;
+
+        if let Some(description) = &result.description {
+            println!("{}", description.dimmed());
+        }
+        for message in result.messages.iter() {
+            let role = match message.role {
+                Role::User => "user",
+                Role::Assistant => "assistant",
+            };
+            println!("{}", format!("[{role}]").bold());
+            println!("{}", format_content_block(&message.content).join("\n"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+
+    #[test]
+    fn test_parse_explore_command() {
+        assert_eq!(ExploreCommand::parse("  "), ExploreCommand::Empty);
+        assert_eq!(
+            ExploreCommand::parse("tools issue"),
+            ExploreCommand::List(ItemKind::Tool, Some("issue".to_string()))
+        );
+        assert_eq!(
+            ExploreCommand::parse("templates"),
+            ExploreCommand::List(ItemKind::ResourceTemplate, None)
+        );
+        assert_eq!(
+            ExploreCommand::parse(r#"call search_issues {"query": "bug"}"#),
+            ExploreCommand::Call(
+                "search_issues".to_string(),
+                Some(r#"{"query": "bug"}"#.to_string())
+            )
+        );
+        assert_eq!(
+            ExploreCommand::parse("prompt summarize_issue id=7 style=short"),
+            ExploreCommand::Prompt(
+                "summarize_issue".to_string(),
+                vec![
+                    ("id".to_string(), "7".to_string()),
+                    ("style".to_string(), "short".to_string())
+                ]
+            )
+        );
+        assert!(matches!(
+            ExploreCommand::parse("prompt greeting who"),
+            ExploreCommand::Invalid(_)
+        ));
+        assert!(matches!(
+            ExploreCommand::parse("schema"),
+            ExploreCommand::Invalid(_)
+        ));
+        assert!(matches!(
+            ExploreCommand::parse("list"),
+            ExploreCommand::Invalid(_)
+        ));
+        assert_eq!(ExploreCommand::parse("exit"), ExploreCommand::Quit);
+    }
+
+    #[test]
+    fn test_search_items() {
+        let server_info = sample_server_info().unwrap();
+
+        let names = |items: Vec<(ItemKind, String, String)>| -> Vec<String> {
+            items
+                .into_iter()
+                .map(|(kind, name, _)| format!("{kind} {name}"))
+                .collect()
+        };
+
+        // queries match names, titles, descriptions and uris, ignoring case
+        assert_eq!(
+            names(search_items(&server_info, None, Some("ISSUE"))),
+            vec![
+                "tool search_issues",
+                "tool delete_issue",
+                "prompt summarize_issue",
+                "resource template issue"
+            ]
+        );
+        assert_eq!(
+            names(search_items(&server_info, None, Some("logo.png"))),
+            vec!["resource logo"]
+        );
+        assert_eq!(
+            search_items(&server_info, Some(ItemKind::Prompt), None).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_schema_tree() {
+        let server_info = sample_server_info().unwrap();
+        let tool = &server_info.tools.unwrap()[0];
+
+        let tree = schema_tree(tool);
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines[0], "search_issues");
+        for expected in [
+            "├── query: string (required)",
+            "├── labels: array",
+            "├── limit: integer",
+            "│   └── items: string",
+            "│   └── name: string (required)",
+            "│   ├── asc",
+        ] {
+            assert!(lines.contains(&expected), "missing '{expected}' in\n{tree}");
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Module for filtering the tools, prompts and resources rendered by a render block.
+
+use std::borrow::Cow;
+
+use regex::Regex;
+use rust_mcp_sdk::schema::ToolAnnotations;
+
+use crate::{error::DiscoveryResult, utils::glob_to_regex, McpServerInfo, McpToolMeta};
+
+/// Tool annotation hints that can be used to filter tools, e.g. `read-only=true`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolHint {
+    ReadOnly,
+    Destructive,
+    Idempotent,
+    OpenWorld,
+}
+
+impl ToolHint {
+    /// Parses the name of a hint, as used in render marker properties.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "read-only" => Some(Self::ReadOnly),
+            "destructive" => Some(Self::Destructive),
+            "idempotent" => Some(Self::Idempotent),
+            "open-world" => Some(Self::OpenWorld),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the hint, falling back to the default defined by the MCP specification
+    /// when the tool does not provide it.
+    fn value(&self, annotations: Option<&ToolAnnotations>) -> bool {
+        match self {
+            Self::ReadOnly => annotations.and_then(|a| a.read_only_hint).unwrap_or(false),
+            Self::Destructive => annotations.and_then(|a| a.destructive_hint).unwrap_or(true),
+            Self::Idempotent => annotations.and_then(|a| a.idempotent_hint).unwrap_or(false),
+            Self::OpenWorld => annotations.and_then(|a| a.open_world_hint).unwrap_or(true),
+        }
+    }
+}
+
+/// Filters applied to the server information before rendering a block.
+///
+/// `include` and `exclude` are comma separated lists of patterns, matched against the names of
+/// tools, prompts, resources and resource templates. A pattern enclosed in slashes (`/^admin_/`)
+/// is a regular expression, any other pattern is a glob supporting `*` and `?`.
+/// Hints only apply to tools.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RenderFilter {
+    pub include: Option<String>,
+    pub exclude: Option<String>,
+    pub hints: Vec<(ToolHint, bool)>,
+}
+
+/// Splits a comma separated list of patterns. Commas inside a regular expression enclosed in slashes,
+/// such as `/^a{1,2}$/`, do not split it: a regular expression ends at a slash followed by a comma,
+/// or by the end of the list.
+fn split_patterns(patterns: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut rest = patterns.trim_start();
+    while !rest.is_empty() {
+        let end = if rest.starts_with('/') {
+            rest.char_indices()
+                .skip(1)
+                .filter(|(i, c)| *c == '/' && !rest[..*i].ends_with('\\'))
+                .map(|(i, _)| i + 1)
+                .find(|i| {
+                    rest[*i..].trim_start().is_empty() || rest[*i..].trim_start().starts_with(',')
+                })
+                .unwrap_or(rest.len())
+        } else {
+            rest.find(',').unwrap_or(rest.len())
+        };
+        items.push(rest[..end].trim());
+        rest = rest[end..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    items
+}
+
+/// Compiles a comma separated list of glob or regex patterns into regular expressions.
+pub fn compile_patterns(patterns: &str) -> DiscoveryResult<Vec<Regex>> {
+    split_patterns(patterns)
+        .into_iter()
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            let regex = match pattern.strip_prefix('/').and_then(|p| p.strip_suffix('/')) {
+                Some(regex) => Regex::new(regex)?,
+                None => glob_to_regex(pattern)?,
+            };
+            Ok(regex)
+        })
+        .collect()
+}
+
+impl RenderFilter {
+    /// Returns true if the filter has no effect.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none() && self.hints.is_empty()
+    }
+
+    /// Returns the server information with only the capabilities matching the filter.
+    /// The server information is borrowed as-is when the filter is empty.
+    pub fn apply<'a>(
+        &self,
+        server_info: &'a McpServerInfo,
+    ) -> DiscoveryResult<Cow<'a, McpServerInfo>> {
+        if self.is_empty() {
+            return Ok(Cow::Borrowed(server_info));
+        }
+
+        let include = self.include.as_deref().map(compile_patterns).transpose()?;
+        let exclude = self
+            .exclude
+            .as_deref()
+            .map(compile_patterns)
+            .transpose()?
+            .unwrap_or_default();
+
+        let name_matches = |name: &str| {
+            include
+                .as_ref()
+                .map_or(true, |include| include.iter().any(|re| re.is_match(name)))
+                && !exclude.iter().any(|re| re.is_match(name))
+        };
+
+        let hints_match = |tool: &McpToolMeta| {
+            self.hints
+                .iter()
+                .all(|(hint, expected)| hint.value(tool.annotations.as_ref()) == *expected)
+        };
+
+        let mut filtered = server_info.clone();
+        if let Some(tools) = filtered.tools.as_mut() {
+            tools.retain(|tool| name_matches(&tool.name) && hints_match(tool));
+        }
+        if let Some(prompts) = filtered.prompts.as_mut() {
+            prompts.retain(|prompt| name_matches(&prompt.name));
+        }
+        if let Some(resources) = filtered.resources.as_mut() {
+            resources.retain(|resource| name_matches(&resource.name));
+        }
+        if let Some(resource_templates) = filtered.resource_templates.as_mut() {
+            resource_templates.retain(|template| name_matches(&template.name));
+        }
+
+        Ok(Cow::Owned(filtered))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+
+    fn tool_names(server_info: &McpServerInfo) -> Vec<&str> {
+        server_info
+            .tools
+            .iter()
+            .flatten()
+            .map(|tool| tool.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_filter() {
+        let server_info = sample_server_info().unwrap();
+
+        let filter = RenderFilter::default();
+        assert!(matches!(
+            filter.apply(&server_info).unwrap(),
+            Cow::Borrowed(_)
+        ));
+
+        // glob patterns match whole names, across all capability lists
+        let filter = RenderFilter {
+            include: Some("search_*, read?e".to_string()),
+            ..Default::default()
+        };
+        let filtered = filter.apply(&server_info).unwrap();
+        assert_eq!(tool_names(&filtered), vec!["search_issues"]);
+        assert!(filtered.prompts.as_ref().unwrap().is_empty());
+        assert_eq!(filtered.resources.as_ref().unwrap().len(), 1);
+        assert!(filtered.resource_templates.as_ref().unwrap().is_empty());
+
+        // regex patterns are enclosed in slashes and may match part of a name
+        let filter = RenderFilter {
+            exclude: Some("/issue/".to_string()),
+            ..Default::default()
+        };
+        let filtered = filter.apply(&server_info).unwrap();
+        assert!(tool_names(&filtered).is_empty());
+        assert_eq!(filtered.prompts.as_ref().unwrap().len(), 1);
+
+        // hints only apply to tools
+        let filter = RenderFilter {
+            hints: vec![(ToolHint::Destructive, true)],
+            ..Default::default()
+        };
+        let filtered = filter.apply(&server_info).unwrap();
+        assert_eq!(tool_names(&filtered), vec!["delete_issue"]);
+        assert_eq!(filtered.prompts.as_ref().unwrap().len(), 2);
+
+        let filter = RenderFilter {
+            include: Some("/[/".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.apply(&server_info).is_err());
+    }
+
+    #[test]
+    fn test_compile_patterns() {
+        // commas split the list, except inside a regular expression
+        assert_eq!(
+            split_patterns(" admin_*, /^a{1,2}$/ ,/x,y/,, user_? "),
+            vec!["admin_*", "/^a{1,2}$/", "/x,y/", "", "user_?"]
+        );
+        assert_eq!(split_patterns(r"/a\/,b/, c"), vec![r"/a\/,b/", "c"]);
+
+        let patterns = compile_patterns("/^a{1,2}$/, b*").unwrap();
+        assert_eq!(patterns.len(), 2);
+        assert!(patterns[0].is_match("aa"));
+        assert!(!patterns[0].is_match("aaa"));
+        assert!(patterns[1].is_match("bc"));
+    }
+
+    #[test]
+    fn test_tool_hint_defaults() {
+        // hints missing from the annotations fall back to the defaults of the MCP specification
+        assert!(!ToolHint::ReadOnly.value(None));
+        assert!(ToolHint::Destructive.value(None));
+        assert!(!ToolHint::Idempotent.value(None));
+        assert!(ToolHint::OpenWorld.value(None));
+        assert_eq!(ToolHint::from_name("open-world"), Some(ToolHint::OpenWorld));
+        assert_eq!(ToolHint::from_name("readonly"), None);
+    }
+}
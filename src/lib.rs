@@ -1,13 +1,27 @@
 //! A lightweight CLI tool for discovering and documenting MCP Server capabilities.
 
+mod badge;
+mod call;
+mod completions;
+mod diff;
+mod encoding;
 pub mod error;
+mod explore;
+mod filter;
 mod handler;
+mod prompt_samples;
 mod render_template;
+mod resources;
+mod sample;
 mod schema;
+mod servers;
+mod site;
 mod std_output;
 mod templates;
 mod types;
 mod utils;
+mod validate;
+mod watch;
 
 use rust_mcp_sdk::error::McpSdkError;
 use rust_mcp_sdk::mcp_client::McpClientOptions;
@@ -15,19 +29,33 @@ use rust_mcp_sdk::{mcp_icon, ToMcpClientHandler};
 use serde_json::{to_value, Map, Value};
 pub use templates::OutputTemplate;
 pub use types::{
-    DiscoveryCommand, LogLevel, McpCapabilities, McpServerInfo, McpToolMeta, ParamTypes,
-    PrintOptions, Template, WriteOptions,
+    BadgeOptions, CallOptions, DiffFormat, DiscoveryCommand, DiscoveryOptions, ExploreOptions,
+    ExportOptions, LogLevel, McpCapabilities, McpCompletionSamples, McpCompletionValues,
+    McpPromptSample, McpResourceContent, McpServerInfo, McpToolMeta, OutputOptions, ParamTypes,
+    PrintOptions, RenderOptions, SiteOptions, Template, TemplateOptions, TemplateSection,
+    TemplatesCommand, UpdateOptions, UpdateStatus, ValidateOptions, WriteOptions,
 };
 
 use crate::types::McpTaskSupport;
+use badge::server_badges;
+use call::{build_arguments, format_call_result, validate_arguments};
 use colored::Colorize;
+use completions::uri_template_variables;
+use diff::print_diff;
+use encoding::{encode_text_file, read_text_file};
 use error::{DiscoveryError, DiscoveryResult};
+use explore::ExploreSession;
 use handler::MyClientHandler;
-use render_template::{detect_render_markers, render_template};
+use prompt_samples::{load_prompt_args, sample_arguments};
+use render_template::{detect_render_markers, referenced_servers, render_template};
+use resources::{resource_preview, ResourceUriFilter};
 use rust_mcp_sdk::schema::{
-    ClientCapabilities, ClientElicitation, ClientRoots, ClientSampling, ClientTaskElicitation,
-    ClientTaskSampling, ClientTasks, Implementation, InitializeRequestParams,
-    PaginatedRequestParams, Prompt, ProtocolVersion, Resource, ResourceTemplate,
+    CallToolRequestParams, CallToolResult, ClientCapabilities, ClientElicitation, ClientRoots,
+    ClientSampling, ClientTaskElicitation, ClientTaskSampling, ClientTasks,
+    CompleteRequestArgument, CompleteRequestParams, CompleteRequestRef, GetPromptRequestParams,
+    Implementation, InitializeRequestParams, PaginatedRequestParams, Prompt, PromptReference,
+    ProtocolVersion, ReadResourceRequestParams, Resource, ResourceTemplate,
+    ResourceTemplateReference,
 };
 use rust_mcp_sdk::{
     error::SdkResult,
@@ -35,9 +63,56 @@ use rust_mcp_sdk::{
     McpClient, StdioTransport, TransportOptions,
 };
 use schema::tool_params;
-use std::io::stdout;
-use std::sync::Arc;
+use servers::{ServerConfig, ServersConfig};
+use site::render_site;
+use std::collections::BTreeMap;
+use std::io::{stdout, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std_output::{print_header, print_list, print_summary};
+use templates::{export_template, EXPORT_PARTIALS_DIR};
+use utils::write_atomic;
+use validate::validate_template;
+use watch::{keep_written_files, server_changes, snapshot, wait_for_changes};
+
+/// Runs a template maintenance command. These commands only work with templates and do not launch an MCP server.
+pub fn run_templates_command(command: &TemplatesCommand) -> DiscoveryResult<()> {
+    match command {
+        TemplatesCommand::Export(export_options) => {
+            let files = export_template(export_options)?;
+            for file in files.iter() {
+                println!("{} {}", "Exported:".bold(), file.to_string_lossy());
+            }
+            if let Some(template_file) = files.first() {
+                println!(
+                    "\nUse it with: --template-file {} --partials-dir {}",
+                    template_file.to_string_lossy(),
+                    export_options
+                        .out_dir
+                        .join(EXPORT_PARTIALS_DIR)
+                        .to_string_lossy()
+                );
+            }
+        }
+        TemplatesCommand::Validate(validate_options) => {
+            let template_file = &validate_options.template_file;
+            let problems = validate_template(
+                template_file,
+                &validate_options.templating.render_options()?,
+            )?;
+            if !problems.is_empty() {
+                for problem in problems.iter() {
+                    eprintln!("{problem}");
+                }
+                return Err(DiscoveryError::InvalidTemplate(
+                    template_file.to_string_lossy().into_owned(),
+                ));
+            }
+            println!("{} {}", "Valid:".bold(), template_file.to_string_lossy());
+        }
+    }
+    Ok(())
+}
 
 /// Core struct representing the discovery mechanism for the MCP server.
 pub struct McpDiscovery {
@@ -45,6 +120,8 @@ pub struct McpDiscovery {
     options: DiscoveryCommand,
     /// Collected server capabilities and metadata
     pub server_info: Option<McpServerInfo>,
+    /// Files written by the current run, which do not trigger another run in watch mode
+    written_files: Mutex<Vec<PathBuf>>,
 }
 
 impl McpDiscovery {
@@ -52,25 +129,147 @@ impl McpDiscovery {
         Self {
             options,
             server_info: None,
+            written_files: Mutex::default(),
+        }
+    }
+
+    /// Writes a rendered file, recording it and its backup as written by the current run.
+    fn write_file(
+        &self,
+        path: &Path,
+        content: impl AsRef<[u8]>,
+        backup: bool,
+    ) -> DiscoveryResult<()> {
+        let result = write_atomic(path, content, backup);
+        if let Ok(mut written_files) = self.written_files.lock() {
+            written_files.push(path.to_path_buf());
+            if backup {
+                let mut backup_path = path.as_os_str().to_owned();
+                backup_path.push(".bak");
+                written_files.push(backup_path.into());
+            }
         }
+        result
     }
 
     /// Entry point to execute the discovery workflow based on the command.
     pub async fn start(&mut self) -> DiscoveryResult<()> {
-        // launch mcp server and discover capabilities
+        let watched_paths = self.options.watched_paths()?;
+        if !watched_paths.is_empty() {
+            return self.watch(watched_paths).await;
+        }
+
+        // launch mcp server and discover capabilities, unless every server comes from a servers config
+        // or the command works with the server directly
+        let works_with_server = matches!(
+            self.options,
+            DiscoveryCommand::Call(_) | DiscoveryCommand::Explore(_)
+        );
+        if !self.options.mcp_launch_command().is_empty() && !works_with_server {
+            self.discover().await?;
+        }
+
+        self.run_command().await
+    }
+
+    /// Runs discovery and the command each time the watched paths change, until the process is stopped.
+    /// Errors are reported without stopping the watch, and each run prints the changes of the server
+    /// since the previous run.
+    async fn watch(&mut self, watched_paths: Vec<PathBuf>) -> DiscoveryResult<()> {
+        let mut previous_info: Option<McpServerInfo> = None;
+        let mut watched_paths = watched_paths;
+
+        loop {
+            // the snapshot is taken before the run, so files saved while it runs trigger the next run
+            let mut previous = snapshot(&watched_paths);
+            if let Ok(mut written_files) = self.written_files.lock() {
+                written_files.clear();
+            }
+
+            let result = if self.options.mcp_launch_command().is_empty() {
+                self.run_command().await
+            } else {
+                match self.discover().await {
+                    Ok(_) => self.run_command().await,
+                    Err(error) => Err(error),
+                }
+            };
+
+            match result {
+                Ok(()) => {
+                    if let (Some(previous), Some(current)) = (&previous_info, &self.server_info) {
+                        let changes = server_changes(previous, current);
+                        if changes.is_empty() {
+                            println!(
+                                "{}",
+                                "No changes in the server since the last run.".dimmed()
+                            );
+                        } else {
+                            println!("{}", "Changes since the last run:".bold());
+                            for change in changes.iter() {
+                                println!("  {change}");
+                            }
+                        }
+                    }
+                    if self.server_info.is_some() {
+                        previous_info = self.server_info.clone();
+                    }
+                }
+                Err(error) => eprintln!("{} {error}", "Error:".red().bold()),
+            }
+
+            // files written by the run itself do not trigger another run
+            if let Ok(written_files) = self.written_files.lock() {
+                keep_written_files(&mut previous, &snapshot(&watched_paths), &written_files);
+            }
 
-        self.discover().await?;
+            println!(
+                "{}",
+                "Watching for changes... (press Ctrl+C to stop)".dimmed()
+            );
+
+            let (changed, _) = wait_for_changes(&watched_paths, &previous).await;
+            println!(
+                "{} {}",
+                "Changed:".bold(),
+                changed
+                    .iter()
+                    .map(|path| path.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
 
+            // files matching the update patterns may be added between runs
+            if let Ok(paths) = self.options.watched_paths() {
+                watched_paths = paths;
+            }
+        }
+    }
+
+    /// Runs the command against the discovered server information.
+    async fn run_command(&self) -> DiscoveryResult<()> {
         match &self.options {
             DiscoveryCommand::Create(create_options) => {
                 self.create_document(create_options).await?;
             }
             DiscoveryCommand::Update(update_options) => {
-                self.update_document(update_options).await?;
+                self.update_documents(update_options).await?;
             }
             DiscoveryCommand::Print(print_options) => {
                 self.print_server_capabilities(print_options).await?;
             }
+            DiscoveryCommand::Site(site_options) => {
+                self.create_site(site_options).await?;
+            }
+            DiscoveryCommand::Badges(badge_options) => {
+                self.create_badges(badge_options).await?;
+            }
+            DiscoveryCommand::Call(call_options) => {
+                self.call_tool(call_options).await?;
+            }
+            DiscoveryCommand::Explore(explore_options) => {
+                self.explore(explore_options).await?;
+            }
         };
         Ok(())
     }
@@ -92,7 +291,10 @@ impl McpDiscovery {
                 self.print_server_details()?;
             }
             _ => {
-                let content = template.render_template(server_info)?;
+                let content = template.render_with_options(
+                    server_info,
+                    &print_options.templating.render_options()?,
+                )?;
                 println!("{content}");
             }
         }
@@ -111,9 +313,35 @@ impl McpDiscovery {
 
         let template = create_options.match_template()?;
 
-        let content = template.render_template(server_info)?;
+        let content = template
+            .render_with_options(server_info, &create_options.templating.render_options()?)?;
+
+        if create_options.output.dry_run {
+            let current =
+                match read_text_file(&create_options.filename, create_options.latin1_fallback) {
+                    Ok((content, _)) => Some(content),
+                    // a missing file is printed in full, as a new file
+                    Err(DiscoveryError::ReadFile(_, error))
+                        if error.kind() == ErrorKind::NotFound =>
+                    {
+                        None
+                    }
+                    Err(error) => return Err(error),
+                };
+            print_diff(
+                &create_options.filename,
+                current.as_deref(),
+                &content,
+                &create_options.output.diff_format,
+            );
+            return Ok(());
+        }
 
-        tokio::fs::write(&create_options.filename, content).await?;
+        self.write_file(
+            &create_options.filename,
+            &content,
+            create_options.output.backup,
+        )?;
 
         tracing::info!(
             "File '{}' was created successfully.",
@@ -131,44 +359,292 @@ impl McpDiscovery {
         Ok(())
     }
 
+    /// Updates every file matching the update options, reporting the status of each file.
+    /// Failures are reported without stopping the remaining files from being updated.
+    pub async fn update_documents(&self, update_options: &UpdateOptions) -> DiscoveryResult<()> {
+        let files = update_options.files()?;
+        if files.is_empty() {
+            return Err(DiscoveryError::InvalidPattern(format!(
+                "No files match '{}'",
+                update_options.filenames.join("', '")
+            )));
+        }
+
+        let servers = self
+            .discover_referenced_servers(update_options, &files)
+            .await?;
+
+        let mut failed = 0;
+        for file in files.iter() {
+            let file_options = update_options.file_options(file.to_owned());
+            match self.update_document(&file_options, &servers).await {
+                // a dry run prints the changes to stdout, so statuses are reported separately
+                Ok(status) if update_options.output.dry_run => {
+                    eprintln!("{}: {}", file.to_string_lossy(), status.to_string().bold());
+                }
+                Ok(status) => {
+                    println!("{}: {}", file.to_string_lossy(), status.to_string().bold());
+                }
+                Err(error) => {
+                    failed += 1;
+                    eprintln!(
+                        "{}: {} {error}",
+                        file.to_string_lossy(),
+                        "error:".red().bold()
+                    );
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(DiscoveryError::UpdateFailed(failed, files.len()));
+        }
+        Ok(())
+    }
+
+    /// Discovers each server referenced by the `server` property of render markers in the files, once.
+    /// Servers are looked up in the servers config file, and none are discovered without one.
+    async fn discover_referenced_servers(
+        &self,
+        update_options: &UpdateOptions,
+        files: &[PathBuf],
+    ) -> DiscoveryResult<BTreeMap<String, McpServerInfo>> {
+        let mut servers = BTreeMap::new();
+        let Some(config_file) = &update_options.config else {
+            return Ok(servers);
+        };
+        let config = ServersConfig::load(config_file)?;
+
+        for file in files.iter() {
+            // unreadable files are reported when they are updated
+            let Ok((content, _)) = read_text_file(file, update_options.latin1_fallback) else {
+                continue;
+            };
+            for name in referenced_servers(&content) {
+                if servers.contains_key(&name) {
+                    continue;
+                }
+                let server = config.server(&name)?;
+                let launch_message = format!(
+                    "{} {} ({}) ...",
+                    "Launching:".bold(),
+                    name,
+                    server.launch_command().join(" ")
+                );
+                if self.options.json_output() {
+                    eprintln!("{}", launch_message.bright_green());
+                } else {
+                    println!("{}", launch_message.bright_green());
+                }
+                let server_info = self.discover_server(server).await.map_err(|error| {
+                    DiscoveryError::ServerDiscovery(name.clone(), Box::new(error))
+                })?;
+                servers.insert(name, server_info);
+            }
+        }
+        Ok(servers)
+    }
+
     /// Updates an existing file by replacing only templated sections.
-    pub async fn update_document(&self, update_options: &WriteOptions) -> DiscoveryResult<()> {
+    /// The file is only written when the rendered sections differ from its current content.
+    ///
+    /// Render markers with a `server` property are rendered against the matching server of `servers`.
+    pub async fn update_document(
+        &self,
+        update_options: &WriteOptions,
+        servers: &BTreeMap<String, McpServerInfo>,
+    ) -> DiscoveryResult<UpdateStatus> {
         tracing::trace!("Updating '{}' ", update_options.filename.to_string_lossy());
 
-        let server_info = self
-            .server_info
-            .as_ref()
-            .ok_or(DiscoveryError::NotDiscovered)?;
+        if self.server_info.is_none() && servers.is_empty() {
+            return Err(DiscoveryError::NotDiscovered);
+        }
 
         update_options.validate()?;
 
-        let template_markers = detect_render_markers(update_options, server_info)?;
-        let mut content_lines: Vec<String> = template_markers
-            .content
-            .lines()
-            .map(|s| s.to_owned())
-            .collect();
+        let template_markers =
+            detect_render_markers(update_options, self.server_info.as_ref(), servers)?;
+        let updated_content = template_markers.updated_content();
 
-        for location in template_markers.render_locations.iter().rev() {
-            let new_lines: Vec<String> = location
-                .rendered_template
-                .lines()
-                .map(|s| s.to_owned())
-                .collect();
+        let status = if template_markers.render_locations.is_empty() {
+            UpdateStatus::NoMarkers
+        } else if updated_content == template_markers.content {
+            UpdateStatus::Unchanged
+        } else {
+            UpdateStatus::Updated
+        };
+
+        // fails early, before any diff, when the rendered content cannot be written back
+        let encoded = match status {
+            UpdateStatus::Updated => encode_text_file(
+                &update_options.filename,
+                &updated_content,
+                template_markers.encoding,
+            )?,
+            _ => vec![],
+        };
 
-            content_lines.splice(
-                location.render_location.0..location.render_location.1 - 1,
-                new_lines,
+        if update_options.output.dry_run {
+            // with no render markers, the file is left as-is
+            let new_content = match status {
+                UpdateStatus::NoMarkers => &template_markers.content,
+                _ => &updated_content,
+            };
+            print_diff(
+                &update_options.filename,
+                Some(&template_markers.content),
+                new_content,
+                &update_options.output.diff_format,
             );
         }
 
-        let updated_content = content_lines.join(&template_markers.line_ending);
+        if status != UpdateStatus::Updated || update_options.output.dry_run {
+            return Ok(status);
+        }
 
-        std::fs::write(&update_options.filename, updated_content)?;
-        tracing::info!(
+        self.write_file(
+            &update_options.filename,
+            encoded,
+            update_options.output.backup,
+        )?;
+        // the status of each file is reported by the caller
+        tracing::debug!(
             "File '{}' was updated successfully.",
             update_options.filename.to_string_lossy()
         );
+        Ok(UpdateStatus::Updated)
+    }
+
+    /// Launches the MCP server, calls one of its tools and prints the result.
+    /// Arguments are validated against the input schema of the tool before the call.
+    pub async fn call_tool(&self, call_options: &CallOptions) -> DiscoveryResult<()> {
+        let server = ServerConfig::from_command(&call_options.mcp_server_cmd);
+        let client = self.try_launch_mcp_server(&server).await?;
+
+        let result = self.invoke_tool(Arc::clone(&client), call_options).await;
+
+        if let Err(error) = client.shut_down().await {
+            tracing::trace!("Unable to shut down the server : {}", error);
+        }
+
+        let result = result?;
+        println!("{}", format_call_result(&result));
+
+        if result.is_error == Some(true) {
+            return Err(DiscoveryError::ToolCallFailed(call_options.tool.to_owned()));
+        }
+        Ok(())
+    }
+
+    /// Launches the MCP server and runs an interactive session with it, until the user quits.
+    pub async fn explore(&self, explore_options: &ExploreOptions) -> DiscoveryResult<()> {
+        let server = ServerConfig::from_command(&explore_options.mcp_server_cmd);
+        let client = self.try_launch_mcp_server(&server).await?;
+
+        let result = match self.collect_server_info(Arc::clone(&client)).await {
+            Ok(server_info) => {
+                ExploreSession::new(Arc::clone(&client), server_info)
+                    .run()
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = client.shut_down().await {
+            tracing::trace!("Unable to shut down the server : {}", error);
+        }
+        result
+    }
+
+    /// Builds and validates the arguments of a tool, then calls it.
+    async fn invoke_tool(
+        &self,
+        client: Arc<ClientRuntime>,
+        call_options: &CallOptions,
+    ) -> DiscoveryResult<CallToolResult> {
+        let tools = self.tools(Arc::clone(&client)).await?.unwrap_or_default();
+        let tool = tools
+            .iter()
+            .find(|tool| tool.name == call_options.tool)
+            .ok_or_else(|| {
+                DiscoveryError::ToolNotFound(
+                    call_options.tool.to_owned(),
+                    tools
+                        .iter()
+                        .map(|tool| tool.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            })?;
+
+        let arguments = build_arguments(tool, call_options.args.as_deref(), &call_options.arg)?;
+        let problems = validate_arguments(tool, &arguments);
+        if !problems.is_empty() {
+            return Err(DiscoveryError::InvalidArguments(format!(
+                "Invalid arguments for tool '{}':\n  {}",
+                tool.name,
+                problems.join("\n  ")
+            )));
+        }
+
+        tracing::trace!("calling tool '{}'...", tool.name);
+
+        Ok(client
+            .request_tool_call(CallToolRequestParams {
+                name: tool.name.to_owned(),
+                arguments: Some(arguments),
+                meta: None,
+                task: None,
+            })
+            .await?)
+    }
+
+    /// Generates a multi-page static documentation site in the output directory.
+    pub async fn create_site(&self, site_options: &SiteOptions) -> DiscoveryResult<()> {
+        tracing::trace!(
+            "Generating site in '{}' ",
+            site_options.out_dir.to_string_lossy()
+        );
+
+        let server_info = self
+            .server_info
+            .as_ref()
+            .ok_or(DiscoveryError::NotDiscovered)?;
+
+        let pages = render_site(server_info, site_options.template_dir.as_deref())?;
+
+        for page in pages.iter() {
+            let page_path = site_options.out_dir.join(&page.path);
+            if let Some(parent) = page_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&page_path, &page.content).await?;
+        }
+
+        tracing::info!(
+            "Site with {} files was generated successfully in '{}'.",
+            pages.len(),
+            site_options.out_dir.to_string_lossy(),
+        );
+
+        Ok(())
+    }
+
+    /// Writes SVG badges summarizing the server version, protocol and capabilities.
+    pub async fn create_badges(&self, badge_options: &BadgeOptions) -> DiscoveryResult<()> {
+        let server_info = self
+            .server_info
+            .as_ref()
+            .ok_or(DiscoveryError::NotDiscovered)?;
+
+        tokio::fs::create_dir_all(&badge_options.out_dir).await?;
+
+        for (name, svg) in server_badges(server_info) {
+            let badge_path = badge_options.out_dir.join(format!("{name}.svg"));
+            tokio::fs::write(&badge_path, svg).await?;
+            tracing::info!("Badge '{}' was created.", badge_path.to_string_lossy());
+        }
+
         Ok(())
     }
 
@@ -407,10 +883,195 @@ impl McpDiscovery {
         }
     }
 
+    /// Reads the content of the resources matching the URI filters of the discovery options,
+    /// returning a preview of each one keyed by URI. Resources that fail to read are skipped.
+    async fn resource_contents(
+        &self,
+        client: Arc<ClientRuntime>,
+        resources: &[Resource],
+        discovery_options: &DiscoveryOptions,
+    ) -> DiscoveryResult<BTreeMap<String, McpResourceContent>> {
+        let uri_filter = ResourceUriFilter::new(discovery_options)?;
+        let mut contents = BTreeMap::new();
+
+        for resource in resources.iter().filter(|r| uri_filter.matches(&r.uri)) {
+            tracing::trace!("reading resource {}...", resource.uri);
+
+            let result = client
+                .request_resource_read(ReadResourceRequestParams {
+                    uri: resource.uri.to_owned(),
+                    meta: None,
+                })
+                .await;
+            match result {
+                Ok(result) => {
+                    if let Some(content) = result.contents.first() {
+                        contents.insert(
+                            resource.uri.to_owned(),
+                            resource_preview(content, discovery_options.resource_size_limit),
+                        );
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Unable to read resource '{}' : {}", resource.uri, err);
+                }
+            }
+        }
+        Ok(contents)
+    }
+
+    /// Gets each prompt with sample arguments from the prompt arguments file of the discovery options,
+    /// or placeholders for required arguments, returning the samples keyed by prompt name.
+    /// Prompts that fail to expand are skipped.
+    async fn prompt_samples(
+        &self,
+        client: Arc<ClientRuntime>,
+        prompts: &[Prompt],
+        discovery_options: &DiscoveryOptions,
+    ) -> DiscoveryResult<BTreeMap<String, McpPromptSample>> {
+        let prompt_args = match &discovery_options.prompt_args_file {
+            Some(prompt_args_file) => load_prompt_args(prompt_args_file)?,
+            None => Map::new(),
+        };
+        let mut samples = BTreeMap::new();
+
+        for prompt in prompts.iter() {
+            tracing::trace!("getting prompt {}...", prompt.name);
+
+            let arguments = sample_arguments(prompt, prompt_args.get(&prompt.name));
+            let result = client
+                .request_prompt(GetPromptRequestParams {
+                    name: prompt.name.to_owned(),
+                    arguments: Some(arguments.clone().into_iter().collect()),
+                    meta: None,
+                })
+                .await;
+            match result {
+                Ok(result) => {
+                    samples.insert(
+                        prompt.name.to_owned(),
+                        McpPromptSample {
+                            arguments,
+                            description: result.description,
+                            messages: result.messages,
+                        },
+                    );
+                }
+                Err(err) => {
+                    tracing::warn!("Unable to get prompt '{}' : {}", prompt.name, err);
+                }
+            }
+        }
+        Ok(samples)
+    }
+
+    /// Requests the completion values of an argument for an empty prefix.
+    /// Returns `None` when the request fails or the server suggests no values.
+    async fn completion_values(
+        &self,
+        client: Arc<ClientRuntime>,
+        reference: CompleteRequestRef,
+        argument_name: &str,
+    ) -> Option<McpCompletionValues> {
+        let result = client
+            .request_completion(CompleteRequestParams {
+                argument: CompleteRequestArgument {
+                    name: argument_name.to_string(),
+                    value: String::new(),
+                },
+                context: None,
+                meta: None,
+                ref_: reference,
+            })
+            .await;
+        match result {
+            Ok(result) if !result.completion.values.is_empty() => Some(McpCompletionValues {
+                values: result.completion.values,
+                total: result.completion.total,
+                has_more: result.completion.has_more.unwrap_or(false),
+            }),
+            Ok(_) => None,
+            Err(err) => {
+                tracing::trace!("Unable to complete argument '{}' : {}", argument_name, err);
+                None
+            }
+        }
+    }
+
+    /// Probes the completion values of each prompt argument and each resource template variable.
+    async fn completion_samples(
+        &self,
+        client: Arc<ClientRuntime>,
+        prompts: &[Prompt],
+        resource_templates: &[ResourceTemplate],
+    ) -> McpCompletionSamples {
+        tracing::trace!("probing completions...");
+        let mut samples = McpCompletionSamples::default();
+
+        for prompt in prompts.iter() {
+            for argument in prompt.arguments.iter() {
+                let reference = PromptReference::new(prompt.name.to_owned(), None).into();
+                if let Some(values) = self
+                    .completion_values(Arc::clone(&client), reference, &argument.name)
+                    .await
+                {
+                    samples
+                        .prompts
+                        .entry(prompt.name.to_owned())
+                        .or_default()
+                        .insert(argument.name.to_owned(), values);
+                }
+            }
+        }
+
+        for template in resource_templates.iter() {
+            for variable in uri_template_variables(&template.uri_template) {
+                let reference =
+                    ResourceTemplateReference::new(template.uri_template.to_owned()).into();
+                if let Some(values) = self
+                    .completion_values(Arc::clone(&client), reference, &variable)
+                    .await
+                {
+                    samples
+                        .resource_templates
+                        .entry(template.name.to_owned())
+                        .or_default()
+                        .insert(variable, values);
+                }
+            }
+        }
+        samples
+    }
+
     /// Discovers all MCP server capabilities and stores them internally.
     pub async fn discover(&mut self) -> DiscoveryResult<&McpServerInfo> {
-        let client = self.try_launch_mcp_server().await?;
+        let server = ServerConfig::from_command(self.options.mcp_launch_command());
+        let server_info = self.discover_server(&server).await?;
 
+        self.server_info = Some(server_info);
+
+        Ok(self.server_info.as_ref().unwrap())
+    }
+
+    /// Launches an MCP server and discovers all its capabilities.
+    async fn discover_server(&self, server: &ServerConfig) -> DiscoveryResult<McpServerInfo> {
+        let client = self.try_launch_mcp_server(server).await?;
+
+        let server_info = self.collect_server_info(Arc::clone(&client)).await;
+
+        // stop the server, as watch mode launches it again on every run
+        if let Err(error) = client.shut_down().await {
+            tracing::trace!("Unable to shut down the server : {}", error);
+        }
+
+        server_info
+    }
+
+    /// Collects the capabilities and metadata of a running MCP server.
+    async fn collect_server_info(
+        &self,
+        client: Arc<ClientRuntime>,
+    ) -> DiscoveryResult<McpServerInfo> {
         let server_version = client
             .server_version()
             .ok_or(DiscoveryError::ServerNotInitialized)?;
@@ -466,9 +1127,54 @@ impl McpDiscovery {
         let resources = self.resources(Arc::clone(&client)).await?;
         let resource_templates = self.resource_templates(Arc::clone(&client)).await?;
 
+        let resource_contents = match self.options.discovery_options() {
+            Some(discovery_options) if discovery_options.read_resources => Some(
+                self.resource_contents(
+                    Arc::clone(&client),
+                    resources.as_deref().unwrap_or_default(),
+                    discovery_options,
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        let prompt_samples = match self.options.discovery_options() {
+            Some(discovery_options) if discovery_options.prompt_samples => Some(
+                self.prompt_samples(
+                    Arc::clone(&client),
+                    prompts.as_deref().unwrap_or_default(),
+                    discovery_options,
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        // completions are only probed for commands documenting the server
+        let completion_samples =
+            if capabilities.completions && self.options.discovery_options().is_some() {
+                Some(
+                    self.completion_samples(
+                        Arc::clone(&client),
+                        prompts.as_deref().unwrap_or_default(),
+                        resource_templates.as_deref().unwrap_or_default(),
+                    )
+                    .await,
+                )
+            } else {
+                None
+            };
+
+        let protocol_version = client
+            .server_info()
+            .ok_or(DiscoveryError::ServerNotInitialized)?
+            .protocol_version;
+
         let server_info = McpServerInfo {
             name: server_version.name,
             version: server_version.version,
+            protocol_version: Some(protocol_version),
             title: server_version.title,
             description: server_version.description,
             website_url: server_version.website_url,
@@ -477,15 +1183,16 @@ impl McpDiscovery {
             prompts,
             resources,
             resource_templates,
+            resource_contents,
+            prompt_samples,
+            completion_samples,
         };
 
-        self.server_info = Some(server_info);
-
-        Ok(self.server_info.as_ref().unwrap())
+        Ok(server_info)
     }
 
     // Attempt server launch with multiple protocol versions when the latest protocol is not supported.
-    async fn try_launch_mcp_server(&self) -> SdkResult<Arc<ClientRuntime>> {
+    async fn try_launch_mcp_server(&self, server: &ServerConfig) -> SdkResult<Arc<ClientRuntime>> {
         let protocol_versions = [
             ProtocolVersion::V2025_11_25,
             ProtocolVersion::V2025_06_18,
@@ -493,9 +1200,13 @@ impl McpDiscovery {
         ];
         for version in protocol_versions {
             let current_version = format!("with protocol version: {}", version.to_string().bold(),);
-            println!("{}", current_version.bright_green());
+            if self.options.json_output() {
+                eprintln!("{}", current_version.bright_green());
+            } else {
+                println!("{}", current_version.bright_green());
+            }
 
-            match self.launch_mcp_server(version).await {
+            match self.launch_mcp_server(version, server).await {
                 Ok(client) => return Ok(client),
                 Err(McpSdkError::Protocol { kind: _ }) => {}
                 Err(err) => return Err(err),
@@ -510,6 +1221,7 @@ impl McpDiscovery {
     async fn launch_mcp_server(
         &self,
         protocol_version: ProtocolVersion,
+        server: &ServerConfig,
     ) -> SdkResult<Arc<ClientRuntime>> {
         let client_details: InitializeRequestParams = InitializeRequestParams {
             capabilities: ClientCapabilities{
@@ -543,18 +1255,16 @@ impl McpDiscovery {
             client_details.client_info.version
         );
 
-        let (mcp_command, mcp_args) = self.options.mcp_launch_command().split_at(1);
-
         tracing::trace!(
             "launching command : {} {}",
-            mcp_command.first().map(String::as_ref).unwrap_or(""),
-            mcp_args.join(" ")
+            server.command,
+            server.args.join(" ")
         );
 
         let transport = StdioTransport::create_with_server_launch(
-            mcp_command.first().unwrap(),
-            mcp_args.into(),
-            None,
+            &server.command,
+            server.args.clone(),
+            (!server.env.is_empty()).then(|| server.env.clone()),
             TransportOptions::default(),
         )?;
 
@@ -577,3 +1287,124 @@ impl McpDiscovery {
         Ok(client)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+    use std::fs::{read, read_dir, remove_file, write};
+    use std::path::Path;
+
+    /// Returns the sorted names of the files in a directory.
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn discovery() -> McpDiscovery {
+        McpDiscovery {
+            options: DiscoveryCommand::Create(Default::default()),
+            server_info: Some(sample_server_info().unwrap()),
+            written_files: Mutex::default(),
+        }
+    }
+
+    fn dry_run_options(filename: PathBuf) -> WriteOptions {
+        WriteOptions {
+            filename,
+            mcp_server_cmd: vec!["mcp-server".to_string()],
+            output: OutputOptions {
+                dry_run: true,
+                backup: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_document_dry_run() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("README.md");
+        let content = "# Docs\n<!-- mcp-discovery-render -->\n<!-- mcp-discovery-template -->\n{{name}}\n<!-- mcp-discovery-template-end -->\nstale\n<!-- mcp-discovery-render-end -->\n";
+        write(&file, content).unwrap();
+
+        let discovery = discovery();
+        let mut options = dry_run_options(file.clone());
+        let servers = BTreeMap::new();
+
+        // changes are only reported, the file is neither written nor backed up
+        let status = discovery.update_document(&options, &servers).await;
+        assert_eq!(status.unwrap(), UpdateStatus::Updated);
+        assert_eq!(read(&file).unwrap(), content.as_bytes());
+        assert_eq!(file_names(dir.path()), vec!["README.md"]);
+
+        options.output.dry_run = false;
+        let status = discovery.update_document(&options, &servers).await;
+        assert_eq!(status.unwrap(), UpdateStatus::Updated);
+        let updated = read(&file).unwrap();
+        assert_ne!(updated, content.as_bytes());
+        assert_eq!(file_names(dir.path()), vec!["README.md", "README.md.bak"]);
+        remove_file(dir.path().join("README.md.bak")).unwrap();
+
+        options.output.dry_run = true;
+        let status = discovery.update_document(&options, &servers).await;
+        assert_eq!(status.unwrap(), UpdateStatus::Unchanged);
+        assert_eq!(read(&file).unwrap(), updated);
+        assert_eq!(file_names(dir.path()), vec!["README.md"]);
+
+        let file = dir.path().join("NOTES.md");
+        write(&file, "no markers\n").unwrap();
+        let status = discovery
+            .update_document(&dry_run_options(file.clone()), &servers)
+            .await;
+        assert_eq!(status.unwrap(), UpdateStatus::NoMarkers);
+        assert_eq!(read(&file).unwrap(), b"no markers\n");
+        assert_eq!(file_names(dir.path()), vec!["NOTES.md", "README.md"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_document_dry_run() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let discovery = discovery();
+
+        // a new file is only printed
+        let file = dir.path().join("capabilities.txt");
+        let mut options = dry_run_options(file.clone());
+        options.template = Some(Template::Txt);
+        discovery.create_document(&options).await.unwrap();
+        assert!(file_names(dir.path()).is_empty());
+
+        // an existing file is left as-is
+        write(&file, "previous\n").unwrap();
+        discovery.create_document(&options).await.unwrap();
+        assert_eq!(read(&file).unwrap(), b"previous\n");
+        assert_eq!(file_names(dir.path()), vec!["capabilities.txt"]);
+
+        // files in other encodings are read like updated files, not taken for new files
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("previous\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        write(&file, &utf16).unwrap();
+        discovery.create_document(&options).await.unwrap();
+        assert_eq!(read(&file).unwrap(), utf16);
+
+        // unreadable files are reported
+        write(&file, [0x63, 0x61, 0x66, 0xE9, 0x0A]).unwrap();
+        assert!(matches!(
+            discovery.create_document(&options).await,
+            Err(DiscoveryError::InvalidEncoding(..))
+        ));
+        let options = dry_run_options(dir.path().to_path_buf());
+        assert!(matches!(
+            discovery.create_document(&options).await,
+            Err(DiscoveryError::ReadFile(..))
+        ));
+        assert_eq!(file_names(dir.path()), vec!["capabilities.txt"]);
+    }
+}
@@ -2,13 +2,21 @@ mod cli;
 use clap::Parser;
 use cli::{CliDiscoveryCommand, CliPrintOptions, CommandArguments};
 use colored::Colorize;
-use mcp_discovery::{DiscoveryCommand, LogLevel, McpDiscovery};
+use mcp_discovery::{run_templates_command, DiscoveryCommand, LogLevel, McpDiscovery};
 use tracing_subscriber::{self, EnvFilter};
 
 #[tokio::main]
 async fn main() {
     let args = CommandArguments::parse();
 
+    if let Some(CliDiscoveryCommand::Templates(templates_command)) = args.command {
+        if let Err(error) = run_templates_command(&templates_command.into()) {
+            eprintln!("Error: {error}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let command: DiscoveryCommand = args
         .command
         .unwrap_or(CliDiscoveryCommand::Print(CliPrintOptions {
@@ -16,6 +24,8 @@ async fn main() {
             template: args.template,
             template_file: args.template_file,
             template_string: args.template_string,
+            templating: args.templating,
+            discovery: args.discovery,
             log_level: args.log_level,
         }))
         .into();
@@ -39,7 +49,15 @@ async fn main() {
         &command.mcp_launch_command().join(" "),
     );
 
-    println!("{}", launch_message.bright_green());
+    // servers from a servers config are announced when launched,
+    // and stdout is kept free of messages when it carries machine-readable output
+    if !command.mcp_launch_command().is_empty() {
+        if command.json_output() {
+            eprintln!("{}", launch_message.bright_green());
+        } else {
+            println!("{}", launch_message.bright_green());
+        }
+    }
 
     let mut discovery_agent = McpDiscovery::new(command);
 
@@ -0,0 +1,104 @@
+//! Module for building the sample arguments used to get prompts during discovery, when `--prompt-samples` is set.
+
+use std::{collections::BTreeMap, path::Path};
+
+use rust_mcp_sdk::schema::Prompt;
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{DiscoveryError, DiscoveryResult},
+    utils::load_vars,
+};
+
+/// Loads sample prompt arguments from a JSON or YAML file, as an object of argument values
+/// for each prompt name:
+/// ```yaml
+/// summarize_issue:
+///   id: 42
+///   style: short
+/// ```
+pub fn load_prompt_args(prompt_args_file: &Path) -> DiscoveryResult<Map<String, Value>> {
+    let prompt_args = load_vars(Some(prompt_args_file), &[])?;
+    if let Some((name, _)) = prompt_args.iter().find(|(_, args)| !args.is_object()) {
+        return Err(DiscoveryError::InvalidVars(format!(
+            "Sample arguments of prompt '{name}' in '{}' must be an object of argument values.",
+            prompt_args_file.display()
+        )));
+    }
+    Ok(prompt_args)
+}
+
+/// Returns the arguments to get a prompt with: the sample values provided for the prompt,
+/// then a `<name>` placeholder for each required argument without a sample value.
+pub fn sample_arguments(prompt: &Prompt, samples: Option<&Value>) -> BTreeMap<String, String> {
+    let mut arguments: BTreeMap<String, String> = samples
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value.to_owned(),
+                value => value.to_string(),
+            };
+            (name.to_owned(), value)
+        })
+        .collect();
+
+    for argument in prompt.arguments.iter() {
+        if argument.required == Some(true) && !arguments.contains_key(&argument.name) {
+            arguments.insert(argument.name.to_owned(), format!("<{}>", argument.name));
+        }
+    }
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+    use serde_json::json;
+    use std::fs::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sample_arguments() {
+        let prompts = sample_server_info().unwrap().prompts.unwrap();
+        let summarize_issue = &prompts[0];
+
+        // required arguments fall back to placeholders, optional arguments are left out
+        assert_eq!(
+            sample_arguments(summarize_issue, None),
+            BTreeMap::from([("id".to_string(), "<id>".to_string())])
+        );
+
+        let samples = json!({ "id": 42, "style": "short" });
+        assert_eq!(
+            sample_arguments(summarize_issue, Some(&samples)),
+            BTreeMap::from([
+                ("id".to_string(), "42".to_string()),
+                ("style".to_string(), "short".to_string())
+            ])
+        );
+
+        assert!(sample_arguments(&prompts[1], None).is_empty());
+    }
+
+    #[test]
+    fn test_load_prompt_args() {
+        let dir = TempDir::new().unwrap();
+        let prompt_args_file = dir.path().join("prompts.yaml");
+
+        write(&prompt_args_file, "summarize_issue:\n  id: 42\n").unwrap();
+        let prompt_args = load_prompt_args(&prompt_args_file).unwrap();
+        assert_eq!(
+            prompt_args.get("summarize_issue"),
+            Some(&json!({ "id": 42 }))
+        );
+
+        write(&prompt_args_file, "summarize_issue: 42\n").unwrap();
+        assert!(matches!(
+            load_prompt_args(&prompt_args_file),
+            Err(DiscoveryError::InvalidVars(_))
+        ));
+    }
+}
@@ -1,23 +1,33 @@
 //! Module for rendering templates using Handlebars and handling MCP server template markers.
 
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use handlebars::{
-    handlebars_helper, Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext,
-    RenderError, RenderErrorReason,
+    handlebars_helper, Context, Handlebars, Helper, HelperDef, HelperResult, JsonRender,
+    JsonTruthy, Output, RenderContext, RenderError, RenderErrorReason, ScopedJson,
 };
+use html_escape::encode_safe;
 use regex::Regex;
 use rust_mcp_sdk::schema::Icon;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
+    badge::{badge_svg, BADGE_COLOR_INFO},
+    encoding::read_text_file,
     error::{DiscoveryError, DiscoveryResult},
+    filter::{RenderFilter, ToolHint},
     templates::{InlineTemplateInfo, PARTIALS},
-    types::{ParamTypes, Template, WriteOptions},
+    types::{McpToolMeta, ParamTypes, RenderOptions, Template, TemplateSection, WriteOptions},
     utils::{
-        boolean_indicator, line_ending, match_template, RenderTemplateInfo, UpdateTemplateInfo,
+        self, boolean_indicator, find_template_file, line_ending, match_template,
+        RenderTemplateInfo, UpdateTemplateInfo,
     },
     McpServerInfo, OutputTemplate,
 };
@@ -47,10 +57,42 @@ use crate::{
 /// - `template_file`: `None`
 /// - `template`: `Some(Template::Template::MdPlain)`
 ///
+/// A `partials-dir` property registers every `*.hbs` file of a directory as a partial:
+/// ```text
+/// mcp-discovery-render template=md partials-dir=./partials
+/// ```
+///
+/// A `section` property renders a single section of the built-in template, such as its tools:
+/// ```text
+/// mcp-discovery-render template=md section=tools
+/// ```
+///
+/// Tools, prompts and resources rendered by a block can be filtered by name with `include` and
+/// `exclude` glob or `/regex/` patterns, and tools by their annotation hints:
+/// ```text
+/// mcp-discovery-render template=md include=admin_* exclude=/_internal$/ read-only=false
+/// ```
+///
+/// A `server` property renders the block against a server of the servers config file,
+/// instead of the server launched from the command line:
+/// ```text
+/// mcp-discovery-render template=md section=tools server=github
+/// ```
+///
+/// Block variables are declared as `vars.<key>=value` properties, quoting values with spaces:
+/// ```text
+/// mcp-discovery-render template=md vars.package=my-server vars.install="npm i my-server"
+/// ```
+///
 #[derive(Debug)]
 pub struct RenderTemplateProps {
     pub template_file: Option<PathBuf>,
     pub template: Option<Template>,
+    pub partials_dir: Option<PathBuf>,
+    pub section: Option<String>,
+    pub server: Option<String>,
+    pub filter: RenderFilter,
+    pub vars: Map<String, Value>,
 }
 
 // Constants for template and render marker tags used in files.
@@ -61,10 +103,66 @@ const MCP_DISCOVERY_RENDER_END: &str = "mcp-discovery-render-end";
 const MCP_DISCOVERY_MARKER_REGEX: &str = r"\bmcp-discovery(-template|-render)(-end)?";
 const MCP_DISCOVERY_TEMPLATE_FILE_REGEX: &str =
     r"(template-file=)((?:\.|~)*[\.\w\s/-]+)(?:\s|$|-->|\*/)";
-const MCP_DISCOVERY_TEMPLATE_REGEX: &str = r"(template=)([\w\-\d\-]+)(\s|$)";
+const MCP_DISCOVERY_TEMPLATE_REGEX: &str = r"(template=)([\w\-\d\-]+)(?:\s|$|-->|\*/)";
+const MCP_DISCOVERY_PARTIALS_DIR_REGEX: &str =
+    r"(partials-dir=)((?:\.|~)*[\.\w\s/-]+)(?:\s|$|-->|\*/)";
+const MCP_DISCOVERY_SECTION_REGEX: &str = r"\bsection=([\w-]+)(?:\s|$|-->|\*/)";
+const MCP_DISCOVERY_SERVER_REGEX: &str = r"(?:^|\s)server=([\w./@-]+?)(?:\s|$|-->|\*/)";
+const MCP_DISCOVERY_NAME_FILTER_REGEX: &str =
+    r#"(?:^|\s)(include|exclude)=(?:"([^"]*)"|([^\s"]+))"#;
+const MCP_DISCOVERY_HINT_FILTER_REGEX: &str =
+    r"(?:^|\s)(read-only|destructive|idempotent|open-world)=(true|false)\b";
+const MCP_DISCOVERY_VARS_REGEX: &str =
+    r#"\bvars\.([\w-]+)=(?:"([^"]*)"|([^\s"]+?))(?:\s|$|-->|\*/)"#;
+
+/// Lines opening and closing block comments, such as AsciiDoc `////` blocks,
+/// which may enclose a marker on its own line.
+const BLOCK_COMMENT_OPEN: [&str; 3] = ["////", "<!--", "/*"];
+const BLOCK_COMMENT_CLOSE: [&str; 3] = ["////", "-->", "*/"];
+
+/// Prefix of reStructuredText comments, which need blank lines around the rendered text.
+const RST_COMMENT_PREFIX: &str = "..";
+
+/// Line comment prefixes of doc comments, kept on the lines rendered for a render marker they hold.
+const DOC_COMMENT_PREFIXES: [&str; 2] = ["///", "//!"];
+
+/// Helpers and decorators built into Handlebars, available in every template.
+const HANDLEBARS_HELPERS: [&str; 18] = [
+    "if", "unless", "each", "with", "lookup", "raw", "log", "eq", "ne", "gt", "gte", "lt", "lte",
+    "and", "or", "not", "len", "inline",
+];
+
+/// Escapes a line of text for roff: backslashes, hyphens and double quotes are escaped,
+/// and a line starting with a control character is protected with a zero-width `\&`.
+fn roff_escape(line: &str) -> String {
+    let line = line
+        .replace('\\', "\\e")
+        .replace('-', "\\-")
+        .replace('"', "\\(dq");
+    if line.starts_with('.') || line.starts_with('\'') {
+        format!("\\&{line}")
+    } else {
+        line
+    }
+}
 
 /// Registers custom Handlebars helpers for template rendering.
 pub fn register_helpers(handlebar: &mut Handlebars) {
+    for (name, helper) in helpers() {
+        handlebar.register_helper(name, helper);
+    }
+}
+
+/// Returns the names of all helpers available in templates, including those built into Handlebars.
+pub fn helper_names() -> Vec<&'static str> {
+    HANDLEBARS_HELPERS
+        .into_iter()
+        .chain(helpers().into_iter().map(|(name, _)| name))
+        .collect()
+}
+
+/// Defines the custom Handlebars helpers, as `(name, helper)` pairs.
+fn helpers() -> Vec<(&'static str, Box<dyn HelperDef + Send + Sync>)> {
     // Helper: Adds 1 to an integer value.
     handlebars_helper!(plus_one: |v: i64| format!("{}", v+1));
 
@@ -111,7 +209,7 @@ pub fn register_helpers(handlebar: &mut Handlebars) {
     // Helper: Formats a capability tag with a boolean indicator and optional count.
     handlebars_helper!(capability_tag: |label:Value, supported: Value, count: Option<i64>, is_md: Option<bool>| {
         let count_str = count.map_or("".to_string(), |count| if count>0 {format!(" ({count})")} else{"".to_string()});
-        if supported.as_bool().unwrap_or(false) {
+        if is_supported(&supported) {
 
             if is_md.unwrap_or(false) {
                 format!("{} {}{}", boolean_indicator(true), label.as_str().unwrap(), count_str)
@@ -145,8 +243,8 @@ pub fn register_helpers(handlebar: &mut Handlebars) {
     });
 
     // Helper: Formats a capability with a boolean indicator and optional count.
-    handlebars_helper!(capability: |label:String, supported: Option<bool>, count: Option<i64>| {
-        let supported = supported.unwrap_or(false);
+    handlebars_helper!(capability: |label:String, supported: Value, count: Option<i64>| {
+        let supported = is_supported(&supported);
         let count_str = if supported && count.is_some() {
             format!(" ({})", count.unwrap())
         }
@@ -183,7 +281,204 @@ pub fn register_helpers(handlebar: &mut Handlebars) {
         param_type.to_string()
     });
 
-    let helpers: Vec<(&str, Box<dyn HelperDef + Send + Sync>)> = vec![
+    // Helper: Escapes text for use in roff (man page) output.
+    // Backslashes, hyphens and double quotes are escaped, lines starting with a control character are
+    // protected with a zero-width `\&`, and blank lines become paragraph breaks.
+    handlebars_helper!(roff: |text: Option<String>| {
+        text.unwrap_or_default()
+            .lines()
+            .map(|line| match roff_escape(line.trim()) {
+                line if line.is_empty() => ".PP".to_string(),
+                line => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    // Helper: Escapes text for use in a roff no-fill (`.nf`) block, such as samples and previews.
+    // Text is escaped like `roff`, but indentation and blank lines are kept as-is.
+    handlebars_helper!(roff_nofill: |text: Option<String>| {
+        text.unwrap_or_default()
+            .lines()
+            .map(|line| roff_escape(line.trim_end()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    // Helper: Escapes text for use inside a quoted Mermaid node label.
+    handlebars_helper!(mermaid_escape: |text: Option<String>| {
+        text.unwrap_or_default()
+            .replace('"', "#quot;")
+            .replace('<', "#lt;")
+            .replace('>', "#gt;")
+            .replace(['\r', '\n'], " ")
+    });
+
+    // Helper: Escapes text for use inside a quoted Graphviz (dot) string.
+    handlebars_helper!(dot_escape: |text: Option<String>| {
+        text.unwrap_or_default()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\r', "")
+            .replace('\n', "\\n")
+    });
+
+    // Helper: Collapses all whitespace, including line breaks, into single spaces.
+    handlebars_helper!(one_line: |text: Option<String>| {
+        text.unwrap_or_default().split_whitespace().collect::<Vec<_>>().join(" ")
+    });
+
+    // Helper: Builds a compact call signature for a tool, e.g. `read(path: string, limit?: number)`.
+    // Parameters missing from the `required` list of the input schema are marked with `?`.
+    handlebars_helper!(tool_signature: |tool: McpToolMeta| {
+        let params = tool
+            .params
+            .iter()
+            .map(|param| {
+                let optional = if tool.input_schema.required.contains(&param.param_name) {
+                    ""
+                } else {
+                    "?"
+                };
+                format!("{}{optional}: {}", param.param_name, param.param_type)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({params})", tool.name)
+    });
+
+    // Helper: Generates an inline shields-style SVG badge, e.g. `{{{badge "tools" (len tools)}}}`.
+    handlebars_helper!(badge: |label: String, value: Value, color: Option<String>| {
+        let value = match value {
+            Value::String(value) => value,
+            Value::Null => "none".to_string(),
+            value => value.to_string(),
+        };
+        badge_svg(&label, &value, color.as_deref().unwrap_or(BADGE_COLOR_INFO))
+    });
+
+    // Logical helpers (`eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `and`, `or`, `not` and `len`)
+    // are built into Handlebars and available in every template.
+
+    // Helper: Sorts a list of objects by the value at a dotted path, e.g. `(sort_by tools "name" desc=true)`.
+    handlebars_helper!(sort_by: |list: Json, path: str, {desc: bool = false}| {
+        let mut items = list.as_array().cloned().unwrap_or_default();
+        items.sort_by(|a, b| compare_values(value_at(a, path), value_at(b, path)));
+        if desc {
+            items.reverse();
+        }
+        items
+    });
+
+    // Helper: Keeps the items of a list whose value at a dotted path is truthy,
+    // or equal to the optional third parameter, e.g. `(filter_by tools "annotations.readOnlyHint")`.
+    handlebars_helper!(filter_by: |list: Json, path: str, *args| {
+        let expected = args.get(2);
+        list.as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| {
+                        let value = value_at(item, path);
+                        match expected {
+                            Some(expected) => value.unwrap_or(&Value::Null) == *expected,
+                            None => value.is_some_and(|v| v.is_truthy(false)),
+                        }
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+
+    // Helper: Groups a list of objects by the value at a dotted path, keeping the order of first appearance.
+    // With a `separator`, items are grouped by the text before it, e.g. `(group_by tools "name" separator="_")`.
+    // Each group is an object with `key` and `items` fields.
+    handlebars_helper!(group_by: |list: Json, path: str, {separator: str = ""}| {
+        let mut groups: Vec<(Value, Vec<Value>)> = vec![];
+        for item in list.as_array().into_iter().flatten() {
+            let value = value_at(item, path).cloned().unwrap_or(Value::Null);
+            let key = match (&value, separator.is_empty()) {
+                (Value::String(text), false) => {
+                    Value::String(text.split(separator).next().unwrap_or_default().to_string())
+                }
+                _ => value,
+            };
+            match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+                Some((_, items)) => items.push(item.clone()),
+                None => groups.push((key, vec![item.clone()])),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(key, items)| serde_json::json!({ "key": key, "items": items }))
+            .collect::<Vec<_>>()
+    });
+
+    // Helper: Shortens text to a maximum number of characters, appending a suffix when truncated.
+    handlebars_helper!(truncate: |text: Option<String>, length: u64, {suffix: str = "…"}| {
+        let text = text.unwrap_or_default();
+        let length = length as usize;
+        if text.chars().count() <= length {
+            text
+        } else {
+            let keep = length.saturating_sub(suffix.chars().count());
+            format!("{}{suffix}", text.chars().take(keep).collect::<String>().trim_end())
+        }
+    });
+
+    // Helper: Converts text into a lowercase slug, suitable for anchors and file names.
+    handlebars_helper!(slugify: |text: Option<String>| {
+        utils::slugify(&text.unwrap_or_default())
+    });
+
+    // Helper: Escapes characters that have a special meaning in markdown, including `|` in tables.
+    handlebars_helper!(markdown_escape: |text: Option<String>| {
+        text.unwrap_or_default()
+            .chars()
+            .fold(String::new(), |mut escaped, c| {
+                if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#' | '~') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+                escaped
+            })
+    });
+
+    // Helper: Escapes text for HTML content and attributes, for use with triple braces.
+    handlebars_helper!(html_escape: |text: Option<String>| {
+        encode_safe(&text.unwrap_or_default()).into_owned()
+    });
+
+    // Helper: Counts the items of a list, or only those whose value at the optional dotted path is truthy.
+    handlebars_helper!(count_helper: |list: Json, *args| {
+        let items = list.as_array().map(|items| items.as_slice()).unwrap_or_default();
+        match args.get(1).and_then(|path| path.as_str()) {
+            Some(path) => items
+                .iter()
+                .filter(|item| value_at(item, path).is_some_and(|v| v.is_truthy(false)))
+                .count(),
+            None => items.len(),
+        }
+    });
+
+    // Helper: Joins the items of a list with a separator, or the values at the optional dotted path,
+    // e.g. `{{join (filter_by tools "annotations.destructiveHint") ", " "name"}}`.
+    handlebars_helper!(join: |list: Json, separator: str, *args| {
+        let path = args.get(2).and_then(|path| path.as_str());
+        list.as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| match path {
+                Some(path) => value_at(item, path),
+                None => Some(item),
+            })
+            .map(|value| value.render())
+            .collect::<Vec<_>>()
+            .join(separator)
+    });
+
+    let helpers: Vec<(&'static str, Box<dyn HelperDef + Send + Sync>)> = vec![
         ("plus_one", Box::new(plus_one)),
         ("underline", Box::new(underline)),
         ("format_text", Box::new(format_text)),
@@ -194,10 +489,80 @@ pub fn register_helpers(handlebar: &mut Handlebars) {
         ("replace_regex", Box::new(replace_regex)),
         ("tool_param_type", Box::new(tool_param_type)),
         ("json", Box::new(json_helper)),
+        ("roff", Box::new(roff)),
+        ("roff_nofill", Box::new(roff_nofill)),
+        ("mermaid_escape", Box::new(mermaid_escape)),
+        ("dot_escape", Box::new(dot_escape)),
+        ("one_line", Box::new(one_line)),
+        ("tool_signature", Box::new(tool_signature)),
+        ("badge", Box::new(badge)),
+        ("sort_by", Box::new(sort_by)),
+        ("filter_by", Box::new(filter_by)),
+        ("group_by", Box::new(group_by)),
+        ("truncate", Box::new(truncate)),
+        ("slugify", Box::new(slugify)),
+        ("markdown_escape", Box::new(markdown_escape)),
+        ("html_escape", Box::new(html_escape)),
+        ("count", Box::new(count_helper)),
+        ("join", Box::new(join)),
+        ("get", Box::new(GetHelper)),
     ];
-    // Register each helper with the Handlebars instance.
-    for (name, helper) in helpers {
-        handlebar.register_helper(name, helper);
+    helpers
+}
+
+/// Returns true if a capability is supported: a `true` flag, or an object of flags with any of them set,
+/// such as the task support of a server.
+fn is_supported(value: &Value) -> bool {
+    match value {
+        Value::Object(flags) => flags.values().any(|flag| flag.as_bool() == Some(true)),
+        value => value.as_bool().unwrap_or(false),
+    }
+}
+
+/// Returns the value at a dotted path (e.g. `annotations.readOnlyHint`), indexing arrays by number.
+fn value_at<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|key| !key.is_empty())
+        .try_fold(value, |value, key| match value {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => value.get(key),
+        })
+}
+
+/// Orders two optional JSON values, placing missing values and values of different types first.
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    }
+}
+
+/// Handlebars helper returning the value of an object at a key, or of an array at an index.
+/// Unlike the built-in `lookup`, a missing object or key gives `null` instead of failing in strict mode,
+/// so templates can look up optional data, e.g. `{{#with (get @root.resource_contents uri)}}`.
+struct GetHelper;
+
+impl HelperDef for GetHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let value = match (h.param(0).map(|p| p.value()), h.param(1).map(|p| p.value())) {
+            (Some(Value::Object(map)), Some(Value::String(key))) => map.get(key),
+            (Some(Value::Array(items)), Some(index)) => {
+                index.as_u64().and_then(|index| items.get(index as usize))
+            }
+            _ => None,
+        };
+        Ok(ScopedJson::Derived(value.cloned().unwrap_or(Value::Null)))
     }
 }
 
@@ -238,19 +603,79 @@ pub fn register_partials(handlebar: &mut Handlebars) {
     }
 }
 
-/// Renders a template with the provided data using Handlebars.
-pub fn render_template<T>(template: &OutputTemplate, data: &T) -> Result<String, RenderError>
-where
-    T: Serialize,
-{
+/// Creates a Handlebars instance with all custom helpers and built-in partials registered.
+pub fn handlebars_registry() -> Handlebars<'static> {
     let mut handlebar: Handlebars = Handlebars::new();
 
     register_helpers(&mut handlebar);
     register_partials(&mut handlebar);
 
-    let template_content = template.content();
+    handlebar
+}
+
+/// Registers every `*.hbs` file in `partials_dir` as a partial named after its file stem.
+/// Partials registered this way replace built-in partials with the same name.
+pub fn register_partials_dir(
+    handlebar: &mut Handlebars,
+    partials_dir: &Path,
+) -> DiscoveryResult<()> {
+    for entry in std::fs::read_dir(partials_dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("hbs") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        tracing::trace!("Registering partial '{}' from '{}'", name, path.display());
+        let content = std::fs::read_to_string(&path)?;
+        handlebar
+            .register_partial(name, content)
+            .map_err(RenderError::from)?;
+    }
+    Ok(())
+}
+
+/// Renders a template with the provided data using Handlebars.
+/// Partials found in `partials_dir`, if provided, are registered on top of the built-in ones.
+pub fn render_template<T>(
+    template: &OutputTemplate,
+    data: &T,
+    render_options: &RenderOptions,
+) -> DiscoveryResult<String>
+where
+    T: Serialize,
+{
+    let mut handlebar = handlebars_registry();
+    // Strict mode only applies to user-provided templates. Built-in partials they include are
+    // written to render in strict mode too, guarding optional fields and empty lists.
+    let is_builtin =
+        template.builtin_file_name().is_some() || matches!(template, OutputTemplate::Partial(_));
+    handlebar.set_strict_mode(render_options.strict && !is_builtin);
+
+    if let Some(partials_dir) = &render_options.partials_dir {
+        register_partials_dir(&mut handlebar, partials_dir)?;
+    }
+
+    // The template is registered under its name (e.g. the template file path), so that
+    // parse and render errors report where they happened along with the line and column.
+    let template_name = template.name();
+    handlebar
+        .register_template_string(&template_name, template.content()?)
+        .map_err(RenderError::from)?;
+
+    // user-defined variables are exposed under the `vars` namespace of the render context
+    let mut data = serde_json::to_value(data)?;
+    if let Value::Object(context) = &mut data {
+        if !render_options.vars.is_empty() {
+            context.insert(
+                "vars".to_string(),
+                Value::Object(render_options.vars.clone()),
+            );
+        }
+    }
 
-    handlebar.render_template(&template_content, &data)
+    Ok(handlebar.render(&template_name, &data)?)
 }
 
 /// Select the template to be used, considering template and template-file and inline templates
@@ -290,16 +715,99 @@ fn select_template(
         )?))
 }
 
+/// Select the partials directory passed via CLI or set as a property of the render marker.
+/// A directory set on the marker is resolved relative to the file being updated.
+fn select_partials_dir(
+    update_options: &WriteOptions,
+    rendering_props: &RenderTemplateProps,
+) -> DiscoveryResult<Option<PathBuf>> {
+    if let Some(partials_dir) = &update_options.templating.partials_dir {
+        return Ok(Some(partials_dir.to_owned()));
+    }
+    rendering_props
+        .partials_dir
+        .as_ref()
+        .map(|dir| find_template_file(dir, Some(&update_options.filename)))
+        .transpose()
+}
+
+/// Returns true if the line at `line_number` (1-based) is one of the block comment `delimiters`.
+fn is_block_delimiter(content: &str, line_number: Option<usize>, delimiters: &[&str]) -> bool {
+    line_number
+        .filter(|line_number| *line_number > 0)
+        .and_then(|line_number| content.lines().nth(line_number - 1))
+        .is_some_and(|line| delimiters.contains(&line.trim()))
+}
+
+/// Removes the line comment prefix of the template start marker, such as `#`, `//` or `..`,
+/// from the lines of an inline template, so templates can be kept in source files and configs.
+/// Lines are left untouched unless every non-empty line starts with the prefix.
+fn strip_comment_prefix<'a>(lines: &[&'a str], marker_line: &str) -> Vec<&'a str> {
+    let prefix = marker_line
+        .find(MCP_DISCOVERY_TEMPLATE_START)
+        .map(|pos| marker_line[..pos].trim())
+        .unwrap_or_default();
+
+    let is_commented =
+        |line: &&str| line.trim().is_empty() || line.trim_start().starts_with(prefix);
+    if prefix.is_empty() || !lines.iter().all(is_commented) {
+        return lines.to_vec();
+    }
+
+    lines
+        .iter()
+        .map(|line| {
+            let line = line.trim_start().strip_prefix(prefix).unwrap_or_default();
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect()
+}
+
+/// Returns the prefix restoring the position of the lines rendered for a marker line:
+/// its indentation, followed by the comment prefix for doc comments (`///`, `//!`),
+/// whose rendered lines must stay doc comments.
+fn rendered_line_prefix(marker_line: &str) -> String {
+    let comment = marker_line.trim_start();
+    let indent = &marker_line[..marker_line.len() - comment.len()];
+    match DOC_COMMENT_PREFIXES
+        .iter()
+        .find(|prefix| comment.starts_with(*prefix))
+    {
+        Some(prefix) => format!("{indent}{prefix} "),
+        None => indent.to_string(),
+    }
+}
+
+/// Adds `prefix` to each line of `text`, keeping line endings. Empty lines get the prefix without
+/// its trailing whitespace, e.g. `///` for a doc comment.
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    if prefix.is_empty() {
+        return text.to_string();
+    }
+    text.split_inclusive('\n')
+        .map(|line| match line.trim_end_matches(['\r', '\n']) {
+            "" => format!("{}{line}", prefix.trim_end()),
+            _ => format!("{prefix}{line}"),
+        })
+        .collect()
+}
+
 /// Detects and processes template and render markers in a file for updating.
+///
+/// Blocks are rendered against `server_info`, the server launched from the command line,
+/// or against the server of `servers` selected by their `server` property.
 pub fn detect_render_markers(
     update_options: &WriteOptions,
-    server_info: &McpServerInfo,
+    server_info: Option<&McpServerInfo>,
+    servers: &BTreeMap<String, McpServerInfo>,
 ) -> DiscoveryResult<UpdateTemplateInfo> {
-    let content = std::fs::read_to_string(update_options.filename.as_path())
-        .expect("Failed to read test data");
+    let (content, encoding) =
+        read_text_file(&update_options.filename, update_options.latin1_fallback)?;
 
     let line_ending = line_ending(content.as_str(), None).to_owned();
 
+    let render_options = update_options.templating.render_options()?;
+
     let re = Regex::new(MCP_DISCOVERY_MARKER_REGEX)?;
 
     let mut inside_template = false;
@@ -310,9 +818,16 @@ pub fn detect_render_markers(
     let mut render_locations: Vec<RenderTemplateInfo> = vec![];
 
     let mut last_template: Option<OutputTemplate> = None;
+    let mut line_prefix = String::new();
+    let mut is_rst_comment = false;
     let mut rendering_props = RenderTemplateProps {
         template_file: None,
         template: None,
+        partials_dir: None,
+        section: None,
+        server: None,
+        filter: RenderFilter::default(),
+        vars: Map::new(),
     };
 
     for mat in re.captures_iter(&content) {
@@ -381,21 +896,38 @@ pub fn detect_render_markers(
 
                 let start_line = template_markers_start.unwrap();
 
-                let template_content = &content
+                let template_lines: Vec<&str> = content
                     .lines()
                     .skip(start_line)
                     .take(line_number - start_line - 1)
-                    .collect::<Vec<_>>()
-                    .join(&line_ending);
+                    .collect();
+
+                let marker_start = content.lines().nth(start_line - 1).unwrap_or("");
+                let mut marker_end = content
+                    .lines()
+                    .nth(line_number - 1)
+                    .unwrap_or("")
+                    .to_owned();
+
+                // a template inside the block comment of the render marker keeps the line closing the block
+                if is_block_delimiter(
+                    &content,
+                    render_markers_start.map(|line| line - 1),
+                    &BLOCK_COMMENT_OPEN,
+                ) && is_block_delimiter(&content, Some(line_number + 1), &BLOCK_COMMENT_CLOSE)
+                {
+                    marker_end = format!(
+                        "{marker_end}{line_ending}{}",
+                        content.lines().nth(line_number).unwrap_or("")
+                    );
+                }
 
                 last_template = Some(OutputTemplate::InlineTemplate(InlineTemplateInfo {
-                    template: template_content.to_owned(),
-                    marker_start: content.lines().nth(start_line - 1).unwrap_or("").to_owned(),
-                    marker_end: content
-                        .lines()
-                        .nth(line_number - 1)
-                        .unwrap_or("")
-                        .to_owned(),
+                    template: strip_comment_prefix(&template_lines, marker_start)
+                        .join(&line_ending),
+                    source: template_lines.join(&line_ending),
+                    marker_start: marker_start.to_owned(),
+                    marker_end,
                 }));
 
                 template_markers_start = None;
@@ -420,11 +952,23 @@ pub fn detect_render_markers(
                     )));
                 }
 
-                rendering_props =
-                    extract_render_props(content.lines().nth(line_number - 1).unwrap());
+                let marker_line = content.lines().nth(line_number - 1).unwrap();
+                rendering_props = extract_render_props(marker_line);
+                line_prefix = rendered_line_prefix(marker_line);
+                // reStructuredText comments end at a blank line, rendered text would be part of them otherwise
+                is_rst_comment = marker_line.trim_start().starts_with(RST_COMMENT_PREFIX);
 
                 inside_render = true;
                 render_markers_start = Some(line_number);
+
+                // a marker on its own line within a block comment renders after the end of the comment
+                if is_block_delimiter(&content, Some(line_number - 1), &BLOCK_COMMENT_OPEN)
+                    && is_block_delimiter(&content, Some(line_number + 1), &BLOCK_COMMENT_CLOSE)
+                {
+                    render_markers_start = Some(line_number + 1);
+                    line_prefix =
+                        rendered_line_prefix(content.lines().nth(line_number - 2).unwrap());
+                }
             }
             MCP_DISCOVERY_RENDER_END => {
                 if !inside_render {
@@ -473,14 +1017,88 @@ pub fn detect_render_markers(
 
                 inside_render = false;
 
-                let template =
+                let mut template =
                     select_template(update_options, &rendering_props, last_template.take())?;
 
+                // a section renders the matching partial of the built-in template on its own
+                if let Some(section) = &rendering_props.section {
+                    let partial = template
+                        .section_partial(&TemplateSection::from_str(section)?)
+                        .ok_or_else(|| {
+                            DiscoveryError::ParseTemplate(format!(
+                                "Render section ending at line {} in '{}' specifies section '{}', which is not available for the selected template. Sections are only supported by the md, md-plain, html, txt and man templates.",
+                                line_number,
+                                update_options.filename.display(),
+                                section
+                            ))
+                        })?;
+                    template = OutputTemplate::Partial(partial);
+                }
+
+                // variables declared on the render marker take precedence over global variables
+                let mut vars = render_options.vars.clone();
+                vars.extend(rendering_props.vars.clone());
+
+                let render_options = RenderOptions {
+                    partials_dir: select_partials_dir(update_options, &rendering_props)?,
+                    vars,
+                    ..render_options.clone()
+                };
+
+                let server_info = match &rendering_props.server {
+                    Some(server) => servers.get(server).ok_or_else(|| {
+                        DiscoveryError::ParseTemplate(format!(
+                            "Render section ending at line {} in '{}' specifies server '{}', which is not defined in the servers config. Pass the config file with '--config'.",
+                            line_number,
+                            update_options.filename.display(),
+                            server
+                        ))
+                    })?,
+                    None => server_info.ok_or_else(|| {
+                        DiscoveryError::ParseTemplate(format!(
+                            "Render section ending at line {} in '{}' has no 'server' property, and no MCP launch command was provided. Add a 'server' property or the launch command.",
+                            line_number,
+                            update_options.filename.display()
+                        ))
+                    })?,
+                };
+                let server_info = rendering_props.filter.apply(server_info)?;
+
+                // rendered lines are placed like the render marker, in doc comments as well
+                let mut rendered = prefix_lines(
+                    &render_template(&template, &server_info, &render_options)?,
+                    &line_prefix,
+                );
+                if is_rst_comment {
+                    rendered = format!(
+                        "{line_ending}{}{line_ending}{line_ending}",
+                        rendered.trim_end_matches(['\r', '\n'])
+                    );
+                }
+
                 // prepend the inline template before the rendered template, to preserve the inline template
-                let rendered_template = template.render_template(server_info)?;
+                let rendered_template = match &template {
+                    OutputTemplate::InlineTemplate(inline_template_info) => {
+                        format!(
+                            "{}{rendered}",
+                            template.inline_template(inline_template_info)
+                        )
+                    }
+                    _ => rendered,
+                };
+
+                // likewise, rendering stops at the start of the block comment of the end marker
+                let render_markers_end =
+                    if is_block_delimiter(&content, Some(line_number - 1), &BLOCK_COMMENT_OPEN)
+                        && is_block_delimiter(&content, Some(line_number + 1), &BLOCK_COMMENT_CLOSE)
+                    {
+                        line_number - 1
+                    } else {
+                        line_number
+                    };
 
                 render_locations.push(RenderTemplateInfo {
-                    render_location: (render_markers_start.unwrap(), line_number),
+                    render_location: (render_markers_start.unwrap(), render_markers_end),
                     rendered_template,
                 });
             }
@@ -490,6 +1108,7 @@ pub fn detect_render_markers(
 
     Ok(UpdateTemplateInfo {
         content,
+        encoding,
         render_locations,
         line_ending,
     })
@@ -515,10 +1134,105 @@ pub fn extract_template_prop(line: &str) -> Option<Template> {
     template.ok()
 }
 
+/// Extracts a partials directory path from a marker line using a regex.
+pub fn extract_partials_dir(line: &str) -> Option<String> {
+    let re = Regex::new(MCP_DISCOVERY_PARTIALS_DIR_REGEX).unwrap();
+
+    re.captures(line)
+        .and_then(|cap| cap.get(2).map(|m| m.as_str().trim().to_string()))
+}
+
+/// Extracts the section name from a marker line using a regex.
+pub fn extract_section(line: &str) -> Option<String> {
+    let re = Regex::new(MCP_DISCOVERY_SECTION_REGEX).unwrap();
+
+    re.captures(line).map(|cap| cap[1].to_string())
+}
+
+/// Extracts the server name from a marker line using a regex.
+pub fn extract_server(line: &str) -> Option<String> {
+    let re = Regex::new(MCP_DISCOVERY_SERVER_REGEX).unwrap();
+
+    re.captures(line).map(|cap| cap[1].to_string())
+}
+
+/// Returns the servers referenced by the `server` property of the render markers in a file's content,
+/// without duplicates.
+pub fn referenced_servers(content: &str) -> Vec<String> {
+    let re = Regex::new(MCP_DISCOVERY_MARKER_REGEX).unwrap();
+
+    let mut servers: Vec<String> = vec![];
+    for line in content.lines() {
+        let is_render_start = re
+            .find_iter(line)
+            .any(|mat| mat.as_str() == MCP_DISCOVERY_RENDER_START);
+        if let Some(server) = extract_server(line).filter(|_| is_render_start) {
+            if !servers.contains(&server) {
+                servers.push(server);
+            }
+        }
+    }
+    servers
+}
+
+/// Extracts the `include`, `exclude` and tool hint filters of a render marker line.
+pub fn extract_filter(line: &str) -> RenderFilter {
+    let re = Regex::new(MCP_DISCOVERY_NAME_FILTER_REGEX).unwrap();
+
+    let mut filter = RenderFilter::default();
+    for cap in re.captures_iter(line) {
+        // unquoted patterns may be directly followed by the end of an html or block comment,
+        // which only ends a block comment opened on the same line and not closed after the pattern
+        let patterns = match (cap.get(2), cap.get(3)) {
+            (Some(quoted), _) => Some(quoted.as_str().to_string()),
+            (None, Some(unquoted)) => {
+                let pattern = unquoted.as_str().trim_end_matches("-->");
+                let closes_comment = line[..unquoted.start()].contains("/*")
+                    && !line[unquoted.end()..].contains("*/");
+                let pattern = match pattern.strip_suffix("*/") {
+                    Some(pattern) if closes_comment => pattern,
+                    _ => pattern,
+                };
+                Some(pattern.to_string())
+            }
+            _ => None,
+        };
+        match &cap[1] {
+            "include" => filter.include = patterns,
+            _ => filter.exclude = patterns,
+        }
+    }
+
+    let re = Regex::new(MCP_DISCOVERY_HINT_FILTER_REGEX).unwrap();
+    filter.hints = re
+        .captures_iter(line)
+        .filter_map(|cap| Some((ToolHint::from_name(&cap[1])?, &cap[2] == "true")))
+        .collect();
+
+    filter
+}
+
+/// Extracts the `vars.<key>=value` properties of a render marker line.
+pub fn extract_vars(line: &str) -> Map<String, Value> {
+    let re = Regex::new(MCP_DISCOVERY_VARS_REGEX).unwrap();
+
+    re.captures_iter(line)
+        .filter_map(|cap| {
+            let value = cap.get(2).or(cap.get(3))?.as_str();
+            Some((cap[1].to_string(), Value::String(value.to_string())))
+        })
+        .collect()
+}
+
 pub fn extract_render_props(line: &str) -> RenderTemplateProps {
     RenderTemplateProps {
         template: extract_template_prop(line),
         template_file: extract_template_file(line).map(PathBuf::from),
+        partials_dir: extract_partials_dir(line).map(PathBuf::from),
+        section: extract_section(line),
+        server: extract_server(line),
+        filter: extract_filter(line),
+        vars: extract_vars(line),
     }
 }
 
@@ -529,12 +1243,14 @@ mod tests {
     use tempfile::NamedTempFile;
 
     use super::*;
+    use crate::utils::write_atomic;
     use crate::*;
 
     pub fn default_mcp_server_info() -> McpServerInfo {
         McpServerInfo {
             name: Default::default(),
             version: Default::default(),
+            protocol_version: Default::default(),
             capabilities: McpCapabilities {
                 tools: false,
                 prompts: false,
@@ -552,6 +1268,9 @@ mod tests {
             prompts: Default::default(),
             resources: Default::default(),
             resource_templates: Default::default(),
+            resource_contents: None,
+            prompt_samples: None,
+            completion_samples: None,
             title: Default::default(),
             description: Default::default(),
             website_url: Default::default(),
@@ -587,6 +1306,13 @@ mod tests {
             r#"<span style="opacity:0.6" class="error">✘ Feature</span>"#
         );
 
+        // Test capability helper with an object of flags, such as the task support
+        let task = json!({"task": {"tool_call_task": false, "list_task": true}});
+        let result = handlebar
+            .render_template("{{capability \"Tasks\" task null}}", &task)
+            .expect("Failed to render capability");
+        assert_eq!(result, "✔ Tasks");
+
         // Test capability helper
         let result = handlebar
             .render_template("{{{capability \"Feature\" true 42}}}", &json!({}))
@@ -628,28 +1354,485 @@ mod tests {
             )
             .expect("Failed to render tool_param_type");
         assert_eq!(result, "string");
+
+        // Test roff helper
+        let result = handlebar
+            .render_template(
+                "{{{roff text}}}",
+                &json!({"text": ".hidden file\n\nuse C:\\temp - or 'quoted'"}),
+            )
+            .expect("Failed to render roff");
+        assert_eq!(
+            result,
+            "\\&.hidden file\n.PP\nuse C:\\etemp \\- or 'quoted'"
+        );
+        let result = handlebar
+            .render_template("{{{roff text}}}", &json!({"text": "say \"hi\""}))
+            .expect("Failed to render roff");
+        assert_eq!(result, "say \\(dqhi\\(dq");
+
+        // Test roff_nofill helper
+        let result = handlebar
+            .render_template(
+                "{{{roff_nofill text}}}",
+                &json!({"text": "Step 1:\n\n    indented code\n.hidden - \"x\""}),
+            )
+            .expect("Failed to render roff_nofill");
+        assert_eq!(
+            result,
+            "Step 1:\n\n    indented code\n\\&.hidden \\- \\(dqx\\(dq"
+        );
+    }
+
+    #[test]
+    fn test_register_logic_helpers() {
+        let mut handlebar = Handlebars::new();
+        register_helpers(&mut handlebar);
+        let data = json!({"name": "echo", "count": 2, "flag": false});
+
+        let result = handlebar
+            .render_template(
+                "{{#if (and (eq name \"echo\") (ne count 3) (not flag))}}yes{{/if}}",
+                &data,
+            )
+            .expect("Failed to render eq/ne/and/not");
+        assert_eq!(result, "yes");
+
+        let result = handlebar
+            .render_template("{{#if (or flag (eq count 2))}}yes{{else}}no{{/if}}", &data)
+            .expect("Failed to render or");
+        assert_eq!(result, "yes");
+    }
+
+    #[test]
+    fn test_register_collection_helpers() {
+        let mut handlebar = Handlebars::new();
+        register_helpers(&mut handlebar);
+        let data = json!({"tools": [
+            {"name": "fs_write", "size": 3, "annotations": {"readOnlyHint": false, "destructiveHint": true}},
+            {"name": "fs_read", "size": 10, "annotations": {"readOnlyHint": true}},
+            {"name": "echo", "size": 1},
+        ]});
+
+        // Test sort_by helper
+        let result = handlebar
+            .render_template(
+                "{{#each (sort_by tools \"name\")}}{{name}} {{/each}}",
+                &data,
+            )
+            .expect("Failed to render sort_by");
+        assert_eq!(result, "echo fs_read fs_write ");
+        let result = handlebar
+            .render_template(
+                "{{#each (sort_by tools \"size\" desc=true)}}{{size}} {{/each}}",
+                &data,
+            )
+            .expect("Failed to render sort_by desc");
+        assert_eq!(result, "10 3 1 ");
+
+        // Test filter_by helper (truthy and equality)
+        let result = handlebar
+            .render_template(
+                "{{#each (filter_by tools \"annotations.readOnlyHint\")}}{{name}}{{/each}}",
+                &data,
+            )
+            .expect("Failed to render filter_by");
+        assert_eq!(result, "fs_read");
+        let result = handlebar
+            .render_template(
+                "{{#each (filter_by tools \"annotations.readOnlyHint\" false)}}{{name}}{{/each}}",
+                &data,
+            )
+            .expect("Failed to render filter_by with value");
+        assert_eq!(result, "fs_write");
+
+        // Test group_by helper (by name prefix and by annotation)
+        let result = handlebar
+            .render_template(
+                "{{#each (group_by tools \"name\" separator=\"_\")}}{{key}}:{{len items}} {{/each}}",
+                &data,
+            )
+            .expect("Failed to render group_by");
+        assert_eq!(result, "fs:2 echo:1 ");
+        let result = handlebar
+            .render_template(
+                "{{#each (group_by tools \"annotations.readOnlyHint\")}}[{{key}}]{{len items}} {{/each}}",
+                &data,
+            )
+            .expect("Failed to render group_by annotation");
+        assert_eq!(result, "[false]1 [true]1 []1 ");
+
+        // Test count helper
+        let result = handlebar
+            .render_template(
+                "{{count tools}} {{count tools \"annotations.destructiveHint\"}}",
+                &data,
+            )
+            .expect("Failed to render count");
+        assert_eq!(result, "3 1");
+
+        // Test join helper
+        let result = handlebar
+            .render_template(
+                "{{join tools \", \" \"name\"}}|{{join (sort_by tools \"size\") \"-\" \"size\"}}",
+                &data,
+            )
+            .expect("Failed to render join");
+        assert_eq!(result, "fs_write, fs_read, echo|1-3-10");
+        let result = handlebar
+            .render_template("{{join list \"/\"}}", &json!({"list": ["a", "b", 1]}))
+            .expect("Failed to render join without path");
+        assert_eq!(result, "a/b/1");
+    }
+
+    #[test]
+    fn test_register_text_helpers() {
+        let mut handlebar = Handlebars::new();
+        register_helpers(&mut handlebar);
+
+        // Test truncate helper
+        let data = json!({"text": "Reads a file from disk"});
+        let result = handlebar
+            .render_template("{{truncate text 12}}|{{truncate text 50}}", &data)
+            .expect("Failed to render truncate");
+        assert_eq!(result, "Reads a fil…|Reads a file from disk");
+        let result = handlebar
+            .render_template(
+                "{{truncate text 10 suffix=\"...\"}}|{{truncate missing 5}}",
+                &data,
+            )
+            .expect("Failed to render truncate with suffix");
+        assert_eq!(result, "Reads a...|");
+
+        // Test slugify helper
+        let result = handlebar
+            .render_template("{{slugify \"Resource Templates: Files!\"}}", &json!({}))
+            .expect("Failed to render slugify");
+        assert_eq!(result, "resource-templates-files");
+
+        // Test markdown_escape helper
+        let result = handlebar
+            .render_template(
+                "{{{markdown_escape \"a|b *bold* [link] <tag> `code`\"}}}",
+                &json!({}),
+            )
+            .expect("Failed to render markdown_escape");
+        assert_eq!(result, r"a\|b \*bold\* \[link\] \<tag\> \`code\`");
+
+        // Test html_escape helper
+        let result = handlebar
+            .render_template(
+                "<p title=\"{{{html_escape text}}}\">",
+                &json!({"text": "\"x\" & <y>"}),
+            )
+            .expect("Failed to render html_escape");
+        assert_eq!(result, "<p title=\"&quot;x&quot; &amp; &lt;y&gt;\">");
     }
 
     #[test]
     fn test_render_template() {
         let template = OutputTemplate::TemplateString("Hello, {{name}}!".to_string());
         let data = json!({"name": "World"});
-        let result =
-            render_template::render_template(&template, &data).expect("Failed to render template");
+        let result = render_template::render_template(&template, &data, &RenderOptions::default())
+            .expect("Failed to render template");
         assert_eq!(result, "Hello, World!");
 
         // Test with helper
         let template = OutputTemplate::TemplateString("{{plus_one 5}}".to_string());
-        let result = render_template::render_template(&template, &json!({}))
-            .expect("Failed to render template");
+        let result =
+            render_template::render_template(&template, &json!({}), &RenderOptions::default())
+                .expect("Failed to render template");
         assert_eq!(result, "6");
 
         // Test invalid template
         let template = OutputTemplate::TemplateString("{{#invalid}}".to_string());
-        let result = render_template::render_template(&template, &json!({}));
+        let result =
+            render_template::render_template(&template, &json!({}), &RenderOptions::default());
         assert!(result.is_err(), "Expected error for invalid template");
     }
 
+    #[test]
+    fn test_render_template_strict_mode() {
+        let data = json!({"name": "World"});
+        let strict = RenderOptions {
+            strict: true,
+            ..Default::default()
+        };
+
+        // missing fields render as empty text unless strict mode is enabled
+        let template = OutputTemplate::TemplateString("Hello,\n {{nmae}}!".to_string());
+        let result = render_template::render_template(&template, &data, &RenderOptions::default())
+            .expect("Failed to render template");
+        assert_eq!(result, "Hello,\n !");
+
+        let error = render_template::render_template(&template, &data, &strict)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("\"template-string\" line 2, col 2"),
+            "{error}"
+        );
+        assert!(error.contains("nmae"), "{error}");
+
+        // built-in templates are not affected by strict mode
+        let result = OutputTemplate::Md.render_with_options(&default_mcp_server_info(), &strict);
+        assert!(result.is_ok());
+
+        // built-in partials render in strict mode, without optional fields and data
+        let template = OutputTemplate::TemplateString(
+            "{{> summary}}{{> md-tools}}{{> md-prompts}}{{> md-resources}}".to_string(),
+        );
+        let result =
+            render_template::render_template(&template, &default_mcp_server_info(), &strict);
+        assert!(result.is_ok(), "{result:?}");
+
+        // `get` looks up optional data without failing in strict mode, unlike `lookup`
+        let template = OutputTemplate::TemplateString(
+            "{{#with (get samples name)}}{{this}}{{else}}none{{/with}}".to_string(),
+        );
+        let result = render_template::render_template(&template, &data, &strict).unwrap();
+        assert_eq!(result, "none");
+        let data = json!({"name": "World", "samples": {"World": "Hello"}});
+        let result = render_template::render_template(&template, &data, &strict).unwrap();
+        assert_eq!(result, "Hello");
+        let template = OutputTemplate::TemplateString("{{lookup samples \"Moon\"}}".to_string());
+        assert!(render_template::render_template(&template, &data, &strict).is_err());
+    }
+
+    #[test]
+    fn test_render_template_diagnostics() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let template_file = dir.path().join("custom.hbs");
+        write(&template_file, "# {{name}}\n{{#each tools}}\n{{/if}}").unwrap();
+
+        // parse errors report the template file
+        let template = OutputTemplate::CustomTemplate(template_file.clone());
+        let error =
+            render_template::render_template(&template, &json!({}), &RenderOptions::default())
+                .unwrap_err()
+                .to_string();
+        assert!(
+            error.contains(&template_file.to_string_lossy().to_string()),
+            "{error}"
+        );
+        assert!(error.contains(":3:"), "{error}");
+
+        // a missing template file is an error instead of rendered text
+        let template = OutputTemplate::CustomTemplate(dir.path().join("missing.hbs"));
+        let result =
+            render_template::render_template(&template, &json!({}), &RenderOptions::default());
+        assert!(matches!(result, Err(DiscoveryError::LoadTemplate(..))));
+    }
+
+    #[test]
+    fn test_render_man_template() {
+        let mut server_info = default_mcp_server_info();
+        server_info.name = "my-server".to_string();
+        server_info.version = "1.0.0".to_string();
+        server_info.description = Some(".dot leading description".to_string());
+
+        let result = OutputTemplate::Man
+            .render_template(&server_info)
+            .expect("Failed to render man template");
+
+        assert!(result.contains(".TH \"my\\-server\" 7"));
+        assert!(result.contains(".SH NAME\nmy\\-server \\- MCP server capabilities"));
+        assert!(result.contains(".SH DESCRIPTION\n\\&.dot leading description"));
+        assert!(!result.contains(".SH TOOLS"));
+    }
+
+    #[test]
+    fn test_render_resource_contents() {
+        let server_info = crate::sample::sample_server_info().unwrap();
+
+        let result = OutputTemplate::MdPlain
+            .render_template(&server_info)
+            .expect("Failed to render md-plain template");
+        assert!(result.contains("  - Content: 2048 bytes, truncated\n"));
+        assert!(
+            result.contains("<pre># Project<br/><br/>A sample project using &lt;MCP&gt;.</pre>")
+        );
+        assert!(result.contains("  - Content: 4096 bytes, <code>sha256:1b3d5f7a"));
+
+        let result = OutputTemplate::Txt
+            .render_template(&server_info)
+            .expect("Failed to render text template");
+        assert!(result.contains("   Content: 2048 bytes, truncated\n# Project\n"));
+
+        // previews are only rendered for resources that were read
+        let mut server_info = server_info;
+        server_info.resource_contents = None;
+        let result = OutputTemplate::MdPlain
+            .render_template(&server_info)
+            .expect("Failed to render md-plain template");
+        assert!(!result.contains("Content:"));
+    }
+
+    #[test]
+    fn test_render_prompt_samples() {
+        let server_info = crate::sample::sample_server_info().unwrap();
+
+        let result = OutputTemplate::MdPlain
+            .render_template(&server_info)
+            .expect("Failed to render md-plain template");
+        assert!(result.contains("  - Sample <code>id=&lt;id&gt;</code>:\n"));
+        assert!(
+            result.contains("    - **user**: Summarize issue &lt;id&gt;<br/>and its comments.\n")
+        );
+        assert!(result.contains("    - **assistant**: <i>[image]</i>\n"));
+
+        let result = OutputTemplate::LlmsFull
+            .render_template(&server_info)
+            .expect("Failed to render llms-full template");
+        assert!(
+            result.contains("Sample (id=<id>):\n[user] Summarize issue <id>\nand its comments.\n")
+        );
+        assert!(result.contains("Sample:\n[user] Hello!\n"));
+    }
+
+    #[test]
+    fn test_render_completion_samples() {
+        let server_info = crate::sample::sample_server_info().unwrap();
+
+        let result = OutputTemplate::MdPlain
+            .render_template(&server_info)
+            .expect("Failed to render md-plain template");
+        assert!(result.contains(
+            "  - Values of <code>style</code>: <code>short</code>, <code>detailed</code>\n"
+        ));
+        assert!(result.contains(
+            "  - Values of <code>id</code>: <code>1</code>, <code>2</code>, <code>3</code>, …\n"
+        ));
+
+        let result = OutputTemplate::LlmsFull
+            .render_template(&server_info)
+            .expect("Failed to render llms-full template");
+        assert!(result.contains("- style: Summary style (values: short, detailed)\n"));
+        assert!(result.contains("- id (required): Issue to summarize\n"));
+        assert!(result.contains("  - id: 1, 2, 3, ...\n"));
+    }
+
+    #[test]
+    fn test_render_task_capability() {
+        let mut server_info = default_mcp_server_info();
+        let md = OutputTemplate::Md.render_template(&server_info).unwrap();
+        assert!(md.contains("~~<span style=\"opacity:0.6\" class=\"error\">✘ Tasks</span>~~"));
+
+        // tasks are supported when any of the task flags is set
+        server_info.capabilities.task.list_task = true;
+        let md = OutputTemplate::Md.render_template(&server_info).unwrap();
+        assert!(md.contains("| ✔ Tasks |"), "{md}");
+        let txt = OutputTemplate::Txt.render_template(&server_info).unwrap();
+        assert!(txt.contains("✔ Tasks"), "{txt}");
+        let html = OutputTemplate::Html.render_template(&server_info).unwrap();
+        assert!(
+            html.contains("<td><span class=\"success\">✔ Tasks</span></td>"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn test_render_diagram_templates() {
+        let mut server_info = default_mcp_server_info();
+        server_info.name = "my-server".to_string();
+        server_info.version = "1.0.0".to_string();
+        server_info.capabilities.tools = true;
+        server_info.tools = Some(serde_json::from_value(json!([
+            {
+                "name": "read", "params": [], "input_schema": { "type": "object" }, "icons": [],
+                "annotations": { "readOnlyHint": true }
+            },
+            {
+                "name": "delete", "params": [], "input_schema": { "type": "object" }, "icons": [],
+                "annotations": { "destructiveHint": true }
+            },
+            { "name": "issue_list", "params": [], "input_schema": { "type": "object" }, "icons": [] },
+            {
+                "name": "issue_close", "params": [], "input_schema": { "type": "object" }, "icons": [],
+                "annotations": { "destructiveHint": true }
+            }
+        ]))
+        .unwrap());
+
+        let result = OutputTemplate::Mermaid
+            .render_template(&server_info)
+            .expect("Failed to render mermaid template");
+        assert!(result.starts_with("```mermaid\nflowchart LR\n"));
+        assert!(result.contains("    server --> tools\n"));
+        assert!(result.contains("    tool_0_0[\"read\"]:::readOnly\n    tools --> tool_0_0\n"));
+        assert!(result.contains("    tool_1_0[\"delete\"]:::destructive\n"));
+        assert!(!result.contains("prompts"));
+
+        // tools sharing a name prefix are grouped
+        assert!(result.contains(
+            "    subgraph tool_group_2 [\"issue\"]\n        tool_2_0[\"issue_list\"]\n        tool_2_1[\"issue_close\"]:::destructive\n    end\n    tools --> tool_group_2\n"
+        ));
+        assert_eq!(result.matches("subgraph").count(), 1);
+
+        let result = OutputTemplate::Dot
+            .render_template(&server_info)
+            .expect("Failed to render dot template");
+        assert!(result.starts_with("digraph \"my-server\" {\n"));
+        assert!(result.contains(
+            "    tool_0_0 [label=\"read\", fillcolor=\"#e6f4ea\", color=\"#097300\"];\n"
+        ));
+        assert!(result.contains("    tools -> tool_1_0;\n"));
+        assert!(result.contains(
+            "    subgraph cluster_tool_group_2 {\n        label=\"issue\";\n        style=\"rounded,dashed\";\n        color=\"#34495e\";\n        tool_2_0 [label=\"issue_list\"];\n"
+        ));
+        // edges are declared outside the cluster, which would otherwise contain the tools node
+        assert!(result.contains("    }\n    tools -> tool_2_0;\n    tools -> tool_2_1;\n"));
+        assert_eq!(result.matches("subgraph").count(), 1);
+
+        // diagrams can be embedded using the template property of render markers
+        let props = extract_render_props("<!-- mcp-discovery-render template=mermaid -->");
+        assert_eq!(props.template, Some(Template::Mermaid));
+    }
+
+    #[test]
+    fn test_render_llms_templates() {
+        let mut server_info = default_mcp_server_info();
+        server_info.name = "my-server".to_string();
+        server_info.version = "1.0.0".to_string();
+        server_info.description = Some("Does\nthings.".to_string());
+        server_info.capabilities.prompts = true;
+        server_info.prompts = Some(
+            serde_json::from_value(json!([{
+                "name": "greet",
+                "description": "Greets someone",
+                "arguments": [
+                    { "name": "name", "required": true, "description": "Who to greet" },
+                    { "name": "tone" }
+                ]
+            }]))
+            .unwrap(),
+        );
+
+        let result = OutputTemplate::Llms
+            .render_template(&server_info)
+            .expect("Failed to render llms template");
+        assert!(result.starts_with("# my-server\n\n> Does things.\n"));
+        assert!(result.contains("Capabilities: prompts\n"));
+        assert!(result.contains("## Prompts\n\n- greet(name, tone?): Greets someone\n"));
+        assert!(!result.contains("## Tools"));
+
+        let result = OutputTemplate::LlmsFull
+            .render_template(&server_info)
+            .expect("Failed to render llms-full template");
+        assert!(result.contains("### greet(name, tone?)\n\nGreets someone\n"));
+        assert!(result.contains("- name (required): Who to greet\n- tone\n"));
+
+        // llms.txt file names select the matching template
+        let template = match_template(
+            Some(&PathBuf::from("docs/llms-full.txt")),
+            &None,
+            &None,
+            &None,
+        )
+        .unwrap();
+        assert!(matches!(template, OutputTemplate::LlmsFull));
+    }
+
     #[test]
     fn test_detect_render_markers_valid() {
         let file = NamedTempFile::new().unwrap();
@@ -661,14 +1844,11 @@ mod tests {
         write(&file, content).unwrap();
         let options = WriteOptions {
             filename: file.path().to_path_buf(),
-            template: None,
-            template_file: None,
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
         let server_info = default_mcp_server_info();
-        let result = detect_render_markers(&options, &server_info);
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new());
         assert!(result.is_ok(), "Expected valid marker detection");
         let update_info = result.unwrap();
         assert_eq!(update_info.render_locations.len(), 1);
@@ -688,14 +1868,11 @@ mod tests {
         write(&file, content).unwrap();
         let options = WriteOptions {
             filename: file.path().to_path_buf(),
-            template: None,
-            template_file: None,
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
         let server_info = default_mcp_server_info();
-        let result = detect_render_markers(&options, &server_info);
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new());
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -714,14 +1891,11 @@ mod tests {
         write(&file, content).unwrap();
         let options = WriteOptions {
             filename: file.path().to_path_buf(),
-            template: None,
-            template_file: None,
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
         let server_info = default_mcp_server_info();
-        let result = detect_render_markers(&options, &server_info);
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new());
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -740,14 +1914,11 @@ mod tests {
         write(&file, content).unwrap();
         let options = WriteOptions {
             filename: file.path().to_path_buf(),
-            template: None,
-            template_file: None,
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
         let server_info = default_mcp_server_info();
-        let result = detect_render_markers(&options, &server_info);
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new());
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -767,14 +1938,11 @@ mod tests {
         write(&file, content).unwrap();
         let options = WriteOptions {
             filename: file.path().to_path_buf(),
-            template: None,
-            template_file: None,
             mcp_server_cmd: vec!["mcp-server".to_string()],
-            template_string: None,
-            log_level: None,
+            ..Default::default()
         };
         let server_info = default_mcp_server_info();
-        let result = detect_render_markers(&options, &server_info);
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new());
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -782,6 +1950,500 @@ mod tests {
             .contains("both a 'template-file' and an inline template"));
     }
 
+    #[test]
+    fn test_render_template_with_partials_dir() {
+        let partials_dir = tempfile::TempDir::new().unwrap();
+        write(partials_dir.path().join("md-tools.hbs"), "custom tools").unwrap();
+        write(partials_dir.path().join("footer.hbs"), "footer of {{name}}").unwrap();
+        write(partials_dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let mut server_info = default_mcp_server_info();
+        server_info.name = "my-server".to_string();
+        let render_options = RenderOptions {
+            partials_dir: Some(partials_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // built-in partials are overridden by a partial with the same name
+        let result = OutputTemplate::Md
+            .render_with_options(&server_info, &render_options)
+            .expect("Failed to render template");
+        assert!(result.contains("\ncustom tools\n"));
+
+        let template = OutputTemplate::TemplateString("{{> footer}}".to_string());
+        let result = template
+            .render_with_options(&server_info, &render_options)
+            .expect("Failed to render template");
+        assert_eq!(result, "footer of my-server");
+
+        let template = OutputTemplate::TemplateString("{{> notes}}".to_string());
+        let result = template.render_with_options(&server_info, &render_options);
+        assert!(result.is_err(), "Expected only .hbs files to be registered");
+    }
+
+    #[test]
+    fn test_detect_render_markers_partials_dir_prop() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("partials")).unwrap();
+        write(dir.path().join("partials/md-tools.hbs"), "custom tools").unwrap();
+
+        let file = dir.path().join("README.md");
+        let content = "<!-- mcp-discovery-render template=md partials-dir=./partials -->\n<!-- mcp-discovery-render-end -->\n";
+        write(&file, content).unwrap();
+
+        let options = WriteOptions {
+            filename: file,
+            mcp_server_cmd: vec!["mcp-server".to_string()],
+            ..Default::default()
+        };
+        let server_info = default_mcp_server_info();
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new()).unwrap();
+        assert!(result.render_locations[0]
+            .rendered_template
+            .contains("\ncustom tools\n"));
+    }
+
+    #[test]
+    fn test_extract_partials_dir() {
+        let line = "<!-- mcp-discovery-render template=md partials-dir=./docs/partials -->";
+        let result = extract_partials_dir(line);
+        assert_eq!(result, Some("./docs/partials".to_string()));
+
+        let props = extract_render_props(line);
+        assert_eq!(props.template, Some(Template::Md));
+        assert_eq!(props.partials_dir, Some(PathBuf::from("./docs/partials")));
+
+        let line = "mcp-discovery-render template-file=./template.hbs";
+        assert_eq!(extract_partials_dir(line), None);
+    }
+
+    #[test]
+    fn test_extract_section() {
+        let line = "<!-- mcp-discovery-render template=html section=resource-templates-->";
+        assert_eq!(
+            extract_section(line),
+            Some("resource-templates".to_string())
+        );
+
+        let props = extract_render_props(line);
+        assert_eq!(props.template, Some(Template::Html));
+        assert_eq!(props.section, Some("resource-templates".to_string()));
+
+        assert_eq!(extract_section("mcp-discovery-render template=md"), None);
+    }
+
+    #[test]
+    fn test_detect_render_markers_section() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("README.md");
+        let content = "# Docs\n<!-- mcp-discovery-render section=tools -->\n<!-- mcp-discovery-render-end -->\nHand-written prose\n<!-- mcp-discovery-render template=txt section=summary -->\n<!-- mcp-discovery-render-end -->\n";
+        write(&file, content).unwrap();
+
+        let mut options = WriteOptions {
+            filename: file.clone(),
+            mcp_server_cmd: vec!["mcp-server".to_string()],
+            templating: TemplateOptions {
+                strict: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut server_info = default_mcp_server_info();
+        server_info.capabilities.tools = true;
+        server_info.tools = Some(vec![]);
+
+        let handlebar = handlebars_registry();
+        let expected_tools = handlebar
+            .render_template("{{> md-tools}}", &server_info)
+            .unwrap();
+        let expected_summary = handlebar
+            .render_template("{{> txt-summary}}", &server_info)
+            .unwrap();
+
+        // the template is matched by the file extension, or set on the marker
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new()).unwrap();
+        assert_eq!(result.render_locations.len(), 2);
+        assert_eq!(result.render_locations[0].rendered_template, expected_tools);
+        assert_eq!(
+            result.render_locations[1].rendered_template,
+            expected_summary
+        );
+
+        // templates without the section and unknown sections are reported
+        options.template = Some(Template::Mermaid);
+        assert!(matches!(
+            detect_render_markers(&options, Some(&server_info), &BTreeMap::new()),
+            Err(DiscoveryError::ParseTemplate(_))
+        ));
+
+        write(
+            &file,
+            "<!-- mcp-discovery-render section=tool -->\n<!-- mcp-discovery-render-end -->\n",
+        )
+        .unwrap();
+        options.template = None;
+        let error =
+            detect_render_markers(&options, Some(&server_info), &BTreeMap::new()).unwrap_err();
+        assert!(error.to_string().contains("'tool' is not a valid section"));
+    }
+
+    #[test]
+    fn test_detect_render_markers_server() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("README.md");
+        let content = "<!-- mcp-discovery-render server=github -->\n<!-- mcp-discovery-template -->\n{{name}}\n<!-- mcp-discovery-template-end -->\n<!-- mcp-discovery-render-end -->\n<!-- mcp-discovery-render server=time-->\n<!-- mcp-discovery-template -->\n{{name}}\n<!-- mcp-discovery-template-end -->\n<!-- mcp-discovery-render-end -->\n<!-- mcp-discovery-render -->\n<!-- mcp-discovery-template -->\n{{name}}\n<!-- mcp-discovery-template-end -->\n<!-- mcp-discovery-render-end -->\n";
+        write(&file, content).unwrap();
+        assert_eq!(referenced_servers(content), vec!["github", "time"]);
+
+        let options = WriteOptions {
+            filename: file.clone(),
+            mcp_server_cmd: vec![],
+            ..Default::default()
+        };
+
+        let mut servers = BTreeMap::new();
+        for name in ["github", "time"] {
+            let mut server_info = default_mcp_server_info();
+            server_info.name = format!("{name}-server");
+            servers.insert(name.to_string(), server_info);
+        }
+        let default_server = default_mcp_server_info();
+
+        // each block is rendered against its server, blocks without a server use the default one
+        let result = detect_render_markers(&options, Some(&default_server), &servers).unwrap();
+        let rendered: Vec<&str> = result
+            .render_locations
+            .iter()
+            .map(|location| location.rendered_template.trim_end())
+            .collect();
+        assert!(rendered[0].ends_with("github-server"), "{rendered:?}");
+        assert!(rendered[1].ends_with("time-server"), "{rendered:?}");
+        assert!(rendered[2].ends_with(&default_server.name), "{rendered:?}");
+
+        // a block without a server requires the default server, and servers must be discovered
+        let error = detect_render_markers(&options, None, &servers).unwrap_err();
+        assert!(
+            error.to_string().contains("no 'server' property"),
+            "{error}"
+        );
+
+        servers.remove("time");
+        let error = detect_render_markers(&options, Some(&default_server), &servers).unwrap_err();
+        assert!(
+            error.to_string().contains("specifies server 'time'"),
+            "{error}"
+        );
+
+        assert_eq!(
+            extract_server("mcp-discovery-render template=md server=@acme/docs.v2"),
+            Some("@acme/docs.v2".to_string())
+        );
+        assert_eq!(extract_server("mcp-discovery-render mcp-server=x"), None);
+    }
+
+    #[test]
+    fn test_extract_filter() {
+        let line = r#"<!-- mcp-discovery-render template=md include="admin_*, /^user_/" exclude=*_internal read-only=true open-world=false vars.destructive=true-->"#;
+        let filter = extract_filter(line);
+        assert_eq!(filter.include, Some("admin_*, /^user_/".to_string()));
+        assert_eq!(filter.exclude, Some("*_internal".to_string()));
+        assert_eq!(
+            filter.hints,
+            vec![(ToolHint::ReadOnly, true), (ToolHint::OpenWorld, false)]
+        );
+        assert_eq!(extract_render_props(line).filter, filter);
+
+        assert!(extract_filter("<!-- mcp-discovery-render template=md -->").is_empty());
+
+        // the end of a block comment is not part of an unquoted pattern
+        for line in [
+            "/* mcp-discovery-render include=admin_* */",
+            "/* mcp-discovery-render include=admin_**/",
+            "<!-- mcp-discovery-render include=admin_*-->",
+        ] {
+            assert_eq!(extract_filter(line).include, Some("admin_*".to_string()));
+        }
+        assert_eq!(
+            extract_filter("/* mcp-discovery-render include=/^admin.*/ */").include,
+            Some("/^admin.*/".to_string())
+        );
+        assert_eq!(
+            extract_filter("<!-- mcp-discovery-render include=/^admin.*/ -->").include,
+            Some("/^admin.*/".to_string())
+        );
+    }
+
+    #[test]
+    fn test_detect_render_markers_filter() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("README.md");
+        let content = "<!-- mcp-discovery-render include=admin_* -->\n<!-- mcp-discovery-template -->\n{{#each tools}}{{name}} {{/each}}\n<!-- mcp-discovery-template-end -->\n<!-- mcp-discovery-render-end -->\n<!-- mcp-discovery-render read-only=true -->\n<!-- mcp-discovery-template -->\n{{#each tools}}{{name}} {{/each}}\n<!-- mcp-discovery-template-end -->\n<!-- mcp-discovery-render-end -->\n";
+        write(&file, content).unwrap();
+
+        let options = WriteOptions {
+            filename: file,
+            mcp_server_cmd: vec!["mcp-server".to_string()],
+            ..Default::default()
+        };
+        let mut server_info = default_mcp_server_info();
+        let tool = |name: &str, read_only: bool| {
+            serde_json::from_value::<McpToolMeta>(json!({
+                "name": name,
+                "params": [],
+                "input_schema": { "type": "object" },
+                "icons": [],
+                "annotations": { "readOnlyHint": read_only }
+            }))
+            .unwrap()
+        };
+        server_info.tools = Some(vec![
+            tool("admin_reset", false),
+            tool("admin_status", true),
+            tool("user_list", true),
+        ]);
+
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new()).unwrap();
+        assert!(result.render_locations[0]
+            .rendered_template
+            .ends_with("\nadmin_reset admin_status "));
+        assert!(result.render_locations[1]
+            .rendered_template
+            .ends_with("\nadmin_status user_list "));
+    }
+
+    #[test]
+    fn test_extract_vars() {
+        let line = r#"<!-- mcp-discovery-render template-file=./t.hbs vars.package=my-server vars.install="npm i my-server"-->"#;
+        let vars = extract_vars(line);
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars["package"], json!("my-server"));
+        assert_eq!(vars["install"], json!("npm i my-server"));
+
+        let props = extract_render_props(line);
+        assert_eq!(props.template_file, Some(PathBuf::from("./t.hbs")));
+        assert_eq!(props.vars, vars);
+
+        assert!(extract_vars("<!-- mcp-discovery-render template=md -->").is_empty());
+    }
+
+    #[test]
+    fn test_detect_render_markers_vars() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vars_file = dir.path().join("vars.yaml");
+        write(&vars_file, "package: from-file\nenv: prod\n").unwrap();
+
+        let file = dir.path().join("README.md");
+        let content = "<!-- mcp-discovery-render vars.env=dev -->\n<!-- mcp-discovery-template -->\n{{vars.package}} {{vars.env}} {{vars.region}}\n<!-- mcp-discovery-template-end -->\n<!-- mcp-discovery-render-end -->\n";
+        write(&file, content).unwrap();
+
+        let options = WriteOptions {
+            filename: file,
+            mcp_server_cmd: vec!["mcp-server".to_string()],
+            templating: TemplateOptions {
+                defines: vec![("region".to_string(), "eu".to_string())],
+                vars_file: Some(vars_file),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let server_info = default_mcp_server_info();
+        let result = detect_render_markers(&options, Some(&server_info), &BTreeMap::new()).unwrap();
+        assert!(result.render_locations[0]
+            .rendered_template
+            .ends_with("from-file dev eu"));
+    }
+
+    #[test]
+    fn test_detect_render_markers_comment_syntaxes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut server_info = default_mcp_server_info();
+        server_info.name = "my-server".to_string();
+        write(
+            dir.path().join("server.hbs"),
+            "name: {{name}}\nprotocol: \"{{protocol_version}}\"\n",
+        )
+        .unwrap();
+
+        for (file_name, content, expected) in [
+            (
+                "config.yaml",
+                "server:\n  # mcp-discovery-render\n  # mcp-discovery-template\n  # name: {{name}}\n  # mcp-discovery-template-end\n  # mcp-discovery-render-end\n",
+                "server:\n  # mcp-discovery-render\n  # mcp-discovery-template\n  # name: {{name}}\n  # mcp-discovery-template-end\n  name: my-server\n  # mcp-discovery-render-end",
+            ),
+            (
+                "lib.rs",
+                "    /// mcp-discovery-render\n    /// mcp-discovery-template\n    /// Server: `{{name}}`\n    ///\n    /// Done.\n    /// mcp-discovery-template-end\n    /// mcp-discovery-render-end\n    fn main() {}\n",
+                "    /// mcp-discovery-template-end\n    /// Server: `my-server`\n    ///\n    /// Done.\n    /// mcp-discovery-render-end\n    fn main() {}",
+            ),
+            (
+                "values.yaml",
+                "server:\n  # mcp-discovery-render template-file=./server.hbs\n  # mcp-discovery-render-end\nport: 80\n",
+                "server:\n  # mcp-discovery-render template-file=./server.hbs\n  name: my-server\n  protocol: \"\"\n  # mcp-discovery-render-end\nport: 80",
+            ),
+            (
+                "main.rs",
+                "//! Crate docs.\n//!\n//! mcp-discovery-render template=llms\n//! mcp-discovery-render-end\nfn main() {}\n",
+                "//! mcp-discovery-render template=llms\n//! # my-server\n",
+            ),
+            (
+                "main.py",
+                "# mcp-discovery-render\n# mcp-discovery-template\n# TOOLS = [{{#each tools}}\"{{name}}\"{{/each}}]\n# mcp-discovery-template-end\n# mcp-discovery-render-end\n",
+                "# mcp-discovery-template-end\nTOOLS = []\n# mcp-discovery-render-end",
+            ),
+            (
+                "index.rst",
+                ".. mcp-discovery-render\n.. mcp-discovery-template\n.. **{{name}}**\n..\n.. mcp-discovery-template-end\n.. mcp-discovery-render-end\n",
+                ".. mcp-discovery-template-end\n\n**my-server**\n\n.. mcp-discovery-render-end",
+            ),
+            (
+                "index.adoc",
+                "= Docs\n////\nmcp-discovery-render\nmcp-discovery-template\n*{{name}}*\nmcp-discovery-template-end\n////\n////\nmcp-discovery-render-end\n////\n",
+                "mcp-discovery-template-end\n////\n*my-server*\n////\nmcp-discovery-render-end\n////",
+            ),
+            (
+                "index.html",
+                "<!--\nmcp-discovery-render\n-->\n<!--\nmcp-discovery-render-end\n-->\n",
+                "-->\n<!doctype html>",
+            ),
+        ] {
+            let file = dir.path().join(file_name);
+            write(&file, content).unwrap();
+
+            let options = WriteOptions {
+                filename: file.clone(),
+                mcp_server_cmd: vec!["mcp-server".to_string()],
+                ..Default::default()
+            };
+
+            let updated = detect_render_markers(&options, Some(&server_info), &BTreeMap::new())
+                .unwrap()
+                .updated_content();
+            assert!(updated.contains(expected), "{file_name}:\n{updated}");
+
+            // rendered lines keep the file valid: YAML stays in its mapping, doc comments stay doc comments
+            match file_name {
+                "config.yaml" => {
+                    let config: Value = serde_yaml::from_str(&updated).unwrap();
+                    assert_eq!(config["server"]["name"], "my-server");
+                }
+                "values.yaml" => {
+                    let values: Value = serde_yaml::from_str(&updated).unwrap();
+                    assert_eq!(values["server"]["name"], "my-server");
+                    assert_eq!(values["port"], 80);
+                }
+                "lib.rs" => assert!(updated
+                    .lines()
+                    .all(|line| line.starts_with("    ///") || line == "    fn main() {}")),
+                // a comment is followed by a blank line before text, and text by a blank line before a comment
+                "index.rst" => {
+                    let lines: Vec<&str> = updated.lines().collect();
+                    assert!(lines.windows(2).all(|pair| pair[0].is_empty()
+                        || pair[1].is_empty()
+                        || pair[0].starts_with("..") == pair[1].starts_with("..")));
+                }
+                "main.rs" => assert!(updated
+                    .lines()
+                    .all(|line| line.starts_with("//!") || line == "fn main() {}")),
+                _ => {}
+            }
+
+            // updating again gives the same content
+            write(&file, &updated).unwrap();
+            let updated_again = detect_render_markers(&options, Some(&server_info), &BTreeMap::new())
+                .unwrap()
+                .updated_content();
+            assert_eq!(updated, updated_again, "{file_name}");
+        }
+    }
+
+    #[test]
+    fn test_updated_content_preserves_formatting() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("README.md");
+        let mut server_info = default_mcp_server_info();
+        server_info.name = "my-server".to_string();
+
+        let block = |le: &str| {
+            format!("<!-- mcp-discovery-render -->{le}<!-- mcp-discovery-template -->{le}# {{{{name}}}}{le}{{{{name}}}}{le}<!-- mcp-discovery-template-end -->{le}old{le}<!-- mcp-discovery-render-end -->")
+        };
+        let rendered = |le: &str| {
+            format!("<!-- mcp-discovery-render -->{le}<!-- mcp-discovery-template -->{le}# {{{{name}}}}{le}{{{{name}}}}{le}<!-- mcp-discovery-template-end -->{le}# my-server{le}my-server{le}<!-- mcp-discovery-render-end -->")
+        };
+
+        for (name, content, expected) in [
+            (
+                "lf",
+                format!("Intro\n{}\nOutro\n", block("\n")),
+                format!("Intro\n{}\nOutro\n", rendered("\n")),
+            ),
+            (
+                "crlf",
+                format!("Intro\r\n{}\r\nOutro\r\n", block("\r\n")),
+                format!("Intro\r\n{}\r\nOutro\r\n", rendered("\r\n")),
+            ),
+            (
+                "mixed",
+                format!("Intro\r\n{}\nOutro\r\n", block("\n")),
+                format!("Intro\r\n{}\nOutro\r\n", rendered("\n")),
+            ),
+            (
+                "no trailing newline",
+                format!("Intro\n{}", block("\n")),
+                format!("Intro\n{}", rendered("\n")),
+            ),
+            (
+                "bom",
+                format!("\u{feff}{}\n", block("\r\n")),
+                format!("\u{feff}{}\n", rendered("\r\n")),
+            ),
+            (
+                "symlink",
+                format!("Intro\r\n{}\r\nOutro\r\n", block("\r\n")),
+                format!("Intro\r\n{}\r\nOutro\r\n", rendered("\r\n")),
+            ),
+        ] {
+            write(&file, &content).unwrap();
+            // a symbolic link is updated through, leaving the link in place
+            let filename = match name {
+                #[cfg(unix)]
+                "symlink" => {
+                    let link = dir.path().join("link.md");
+                    std::os::unix::fs::symlink("README.md", &link).unwrap();
+                    link
+                }
+                _ => file.clone(),
+            };
+            let options = WriteOptions {
+                filename: filename.clone(),
+                mcp_server_cmd: vec!["mcp-server".to_string()],
+                ..Default::default()
+            };
+
+            let updated = detect_render_markers(&options, Some(&server_info), &BTreeMap::new())
+                .unwrap()
+                .updated_content();
+            assert_eq!(updated, expected, "{name}");
+
+            write_atomic(&filename, &updated, false).unwrap();
+            assert_eq!(std::fs::read_to_string(&file).unwrap(), expected, "{name}");
+            assert_eq!(
+                std::fs::symlink_metadata(&filename)
+                    .unwrap()
+                    .file_type()
+                    .is_symlink(),
+                filename != file,
+                "{name}"
+            );
+
+            // updating an up to date file leaves it unchanged
+            let updated_again =
+                detect_render_markers(&options, Some(&server_info), &BTreeMap::new())
+                    .unwrap()
+                    .updated_content();
+            assert_eq!(updated_again, updated, "{name}");
+        }
+    }
+
     #[test]
     fn test_extract_template_file() {
         let line = "mcp-discovery-render template-file=./template.hbs";
@@ -791,5 +2453,15 @@ mod tests {
         let line = "mcp-discovery-render";
         let result = extract_template_file(line);
         assert_eq!(result, None);
+
+        // properties may be directly followed by the end of the comment
+        assert_eq!(
+            extract_template_prop("<!-- mcp-discovery-render template=md-plain-->"),
+            Some(Template::MdPlain)
+        );
+        assert_eq!(
+            extract_template_prop("/* mcp-discovery-render template=txt*/"),
+            Some(Template::Txt)
+        );
     }
 }
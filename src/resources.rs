@@ -0,0 +1,149 @@
+//! Module for previewing the content of resources during discovery, when `--read-resources` is set.
+
+use base64::Engine;
+use regex::Regex;
+use rust_mcp_sdk::schema::ReadResourceContent;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::DiscoveryResult, filter::compile_patterns, DiscoveryOptions, McpResourceContent,
+};
+
+/// Selects the resources to read by URI, with the include and exclude patterns of the discovery options.
+pub struct ResourceUriFilter {
+    include: Option<Vec<Regex>>,
+    exclude: Vec<Regex>,
+}
+
+impl ResourceUriFilter {
+    pub fn new(discovery_options: &DiscoveryOptions) -> DiscoveryResult<Self> {
+        Ok(Self {
+            include: discovery_options
+                .resource_include
+                .as_deref()
+                .map(compile_patterns)
+                .transpose()?,
+            exclude: discovery_options
+                .resource_exclude
+                .as_deref()
+                .map(compile_patterns)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Returns true if the resource with the given URI is to be read.
+    pub fn matches(&self, uri: &str) -> bool {
+        self.include
+            .as_ref()
+            .map_or(true, |include| include.iter().any(|re| re.is_match(uri)))
+            && !self.exclude.iter().any(|re| re.is_match(uri))
+    }
+}
+
+/// Truncates text to at most `limit` bytes, without splitting a character.
+/// Returns the truncated text, and whether it was truncated.
+fn truncate_text(text: &str, limit: usize) -> (String, bool) {
+    if text.len() <= limit {
+        return (text.to_string(), false);
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    (text[..end].to_string(), true)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Builds the preview of the content of a resource: the text truncated to `size_limit` bytes,
+/// or only the size and hash of binary content. Sizes and hashes always cover the full content.
+pub fn resource_preview(content: &ReadResourceContent, size_limit: usize) -> McpResourceContent {
+    match content {
+        ReadResourceContent::TextResourceContents(resource) => {
+            let (text, truncated) = truncate_text(&resource.text, size_limit);
+            McpResourceContent {
+                mime_type: resource.mime_type.to_owned(),
+                size: resource.text.len(),
+                sha256: sha256_hex(resource.text.as_bytes()),
+                text: Some(text),
+                truncated,
+            }
+        }
+        ReadResourceContent::BlobResourceContents(resource) => {
+            // content that is not valid base64 is summarized as-is
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(&resource.blob)
+                .unwrap_or_else(|_| resource.blob.as_bytes().to_vec());
+            McpResourceContent {
+                mime_type: resource.mime_type.to_owned(),
+                size: bytes.len(),
+                sha256: sha256_hex(&bytes),
+                text: None,
+                truncated: false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_sdk::schema::{BlobResourceContents, TextResourceContents};
+
+    #[test]
+    fn test_resource_uri_filter() {
+        let filter = ResourceUriFilter::new(&DiscoveryOptions {
+            read_resources: true,
+            resource_include: Some("file:///docs/*, /^config:/".to_string()),
+            resource_exclude: Some("*.png".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(filter.matches("file:///docs/guide.md"));
+        assert!(filter.matches("config://settings"));
+        assert!(!filter.matches("file:///docs/logo.png"));
+        assert!(!filter.matches("file:///src/main.rs"));
+
+        assert!(ResourceUriFilter::new(&DiscoveryOptions::default())
+            .unwrap()
+            .matches("file:///src/main.rs"));
+    }
+
+    #[test]
+    fn test_resource_preview() {
+        let text = ReadResourceContent::TextResourceContents(TextResourceContents {
+            uri: "file:///notes.md".to_string(),
+            mime_type: Some("text/markdown".to_string()),
+            text: "café au lait".to_string(),
+            meta: None,
+        });
+        // the limit falls inside 'é', which is kept out of the preview
+        let preview = resource_preview(&text, 4);
+        assert_eq!(preview.text.as_deref(), Some("caf"));
+        assert!(preview.truncated);
+        assert_eq!(preview.size, 13);
+        assert_eq!(preview.mime_type.as_deref(), Some("text/markdown"));
+
+        let preview = resource_preview(&text, 1024);
+        assert_eq!(preview.text.as_deref(), Some("café au lait"));
+        assert!(!preview.truncated);
+
+        let blob = ReadResourceContent::BlobResourceContents(BlobResourceContents {
+            uri: "file:///data.bin".to_string(),
+            mime_type: None,
+            blob: "aGVsbG8=".to_string(),
+            meta: None,
+        });
+        let preview = resource_preview(&blob, 1024);
+        assert_eq!(preview.text, None);
+        assert_eq!(preview.size, 5);
+        assert_eq!(
+            preview.sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Module providing a sample `McpServerInfo`, used to validate templates without launching an MCP server.
+//!
+//! The sample fills every optional field and covers every `ParamTypes` variant, so templates
+//! are exercised against all the data a real server may provide.
+
+use serde_json::{json, to_value, Value};
+
+use crate::{
+    error::DiscoveryResult,
+    schema::tool_params,
+    types::{McpCapabilities, McpTaskSupport, McpToolMeta},
+    McpServerInfo,
+};
+
+/// Builds a sample tool from a JSON tool definition, deriving its params from the input schema.
+fn sample_tool(tool: Value) -> DiscoveryResult<McpToolMeta> {
+    let input_schema: rust_mcp_sdk::schema::ToolInputSchema =
+        serde_json::from_value(tool["inputSchema"].clone())?;
+    let root_schema = to_value(&input_schema)?;
+
+    Ok(McpToolMeta {
+        name: serde_json::from_value(tool["name"].clone())?,
+        title: serde_json::from_value(tool["title"].clone())?,
+        description: serde_json::from_value(tool["description"].clone())?,
+        params: tool_params(&input_schema.properties, &root_schema),
+        input_schema,
+        execution: serde_json::from_value(tool["execution"].clone())?,
+        icons: serde_json::from_value(tool["icons"].clone())?,
+        annotations: serde_json::from_value(tool["annotations"].clone())?,
+        meta: serde_json::from_value(tool["_meta"].clone())?,
+    })
+}
+
+/// Returns a comprehensive sample of the information discovered from an MCP server.
+pub fn sample_server_info() -> DiscoveryResult<McpServerInfo> {
+    let icons = json!([
+        { "src": "https://example.com/icon-light.svg", "mimeType": "image/svg+xml", "sizes": ["any"], "theme": "light" },
+        { "src": "https://example.com/icon-dark.png", "mimeType": "image/png", "sizes": ["48x48"], "theme": "dark" }
+    ]);
+
+    let tools = vec![
+        sample_tool(json!({
+            "name": "search_issues",
+            "title": "Search Issues",
+            "description": "Searches issues matching a `query`.\nResults are sorted by 'order'.",
+            "icons": icons,
+            "annotations": {
+                "title": "Search Issues",
+                "readOnlyHint": true,
+                "destructiveHint": false,
+                "idempotentHint": true,
+                "openWorldHint": false
+            },
+            "execution": { "taskSupport": "optional" },
+            "_meta": { "category": "issues" },
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Text to search for" },
+                    "limit": { "type": "integer", "description": "Maximum number of results" },
+                    "labels": { "type": "array", "items": { "type": "string" } },
+                    "author": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "email": { "type": "string" }
+                        },
+                        "required": ["name"]
+                    },
+                    "order": { "enum": ["asc", "desc"] },
+                    "since": { "anyOf": [{ "type": "string" }, { "type": "integer" }] },
+                    "format": { "oneOf": [{ "const": "json" }, { "const": "text" }] },
+                    "options": {
+                        "allOf": [
+                            { "type": "object", "properties": { "verbose": { "type": "boolean" } } },
+                            { "type": "object", "properties": { "dry_run": { "type": "boolean" } } }
+                        ]
+                    }
+                },
+                "required": ["query"]
+            }
+        }))?,
+        sample_tool(json!({
+            "name": "delete_issue",
+            "title": "Delete Issue",
+            "description": "Deletes an issue permanently.",
+            "icons": [],
+            "annotations": {
+                "title": "Delete Issue",
+                "readOnlyHint": false,
+                "destructiveHint": true,
+                "idempotentHint": false,
+                "openWorldHint": true
+            },
+            "execution": { "taskSupport": "required" },
+            "_meta": null,
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "number", "description": "Issue id" } },
+                "required": ["id"]
+            }
+        }))?,
+    ];
+
+    let prompts = serde_json::from_value(json!([
+        {
+            "name": "summarize_issue",
+            "title": "Summarize Issue",
+            "description": "Summarizes an issue and its comments.",
+            "icons": icons,
+            "arguments": [
+                { "name": "id", "title": "Issue Id", "description": "Issue to summarize", "required": true },
+                { "name": "style", "description": "Summary style", "required": false }
+            ],
+            "_meta": { "category": "issues" }
+        },
+        { "name": "greeting", "arguments": [] }
+    ]))?;
+
+    let resources = serde_json::from_value(json!([
+        {
+            "name": "readme",
+            "title": "Project Readme",
+            "uri": "file:///project/README.md",
+            "description": "The project readme.",
+            "mimeType": "text/markdown",
+            "size": 2048,
+            "icons": icons,
+            "annotations": { "audience": ["user", "assistant"], "priority": 0.8, "lastModified": "2025-01-01T00:00:00Z" }
+        },
+        { "name": "logo", "uri": "file:///project/logo.png", "mimeType": "image/png" }
+    ]))?;
+
+    let resource_templates = serde_json::from_value(json!([
+        {
+            "name": "issue",
+            "title": "Issue",
+            "uriTemplate": "issues://{id}",
+            "description": "An issue by id.",
+            "mimeType": "application/json",
+            "icons": icons,
+            "annotations": { "audience": ["user"], "priority": 0.5 }
+        }
+    ]))?;
+
+    let resource_contents = serde_json::from_value(json!({
+        "file:///project/README.md": {
+            "mimeType": "text/markdown",
+            "size": 2048,
+            "sha256": "9f2c4e0b7d1a5c3e8b6f4a2d0c9e7b5a3f1d8c6e4b2a0f9d7c5e3b1a8f6d4c2e",
+            "text": "# Project\n\nA sample project using <MCP>.",
+            "truncated": true
+        },
+        "file:///project/logo.png": {
+            "mimeType": "image/png",
+            "size": 4096,
+            "sha256": "1b3d5f7a9c2e4b6d8f0a1c3e5b7d9f2a4c6e8b0d1f3a5c7e9b2d4f6a8c0e1b3d",
+            "truncated": false
+        }
+    }))?;
+
+    let prompt_samples = serde_json::from_value(json!({
+        "summarize_issue": {
+            "arguments": { "id": "<id>" },
+            "description": "Summary of issue <id>",
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "Summarize issue <id>\nand its comments." } },
+                { "role": "assistant", "content": { "type": "image", "data": "aGk=", "mimeType": "image/png" } }
+            ]
+        },
+        "greeting": {
+            "arguments": {},
+            "messages": [{ "role": "user", "content": { "type": "text", "text": "Hello!" } }]
+        }
+    }))?;
+
+    let completion_samples = serde_json::from_value(json!({
+        "prompts": {
+            "summarize_issue": {
+                "style": { "values": ["short", "detailed"], "total": 2 }
+            }
+        },
+        "resource_templates": {
+            "issue": {
+                "id": { "values": ["1", "2", "3"], "total": 42, "hasMore": true }
+            }
+        }
+    }))?;
+
+    Ok(McpServerInfo {
+        name: "sample-server".to_string(),
+        title: Some("Sample Server".to_string()),
+        description: Some(
+            "A sample MCP server, covering all the discovered information.".to_string(),
+        ),
+        website_url: Some("https://example.com".to_string()),
+        version: "1.0.0".to_string(),
+        protocol_version: Some("2025-11-25".to_string()),
+        capabilities: McpCapabilities {
+            tools: true,
+            prompts: true,
+            resources: true,
+            logging: true,
+            completions: true,
+            experimental: true,
+            task: McpTaskSupport {
+                tool_call_task: true,
+                list_task: true,
+                cancel_task: true,
+            },
+        },
+        tools: Some(tools),
+        prompts: Some(prompts),
+        resources: Some(resources),
+        resource_templates: Some(resource_templates),
+        resource_contents: Some(resource_contents),
+        prompt_samples: Some(prompt_samples),
+        completion_samples: Some(completion_samples),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParamTypes;
+
+    #[test]
+    fn test_sample_server_info_param_types() {
+        let server_info = sample_server_info().expect("Failed to build sample server info");
+        let tools = server_info.tools.unwrap();
+        let param_types: Vec<_> = tools[0]
+            .params
+            .iter()
+            .map(|param| match param.param_type {
+                ParamTypes::Primitive(_) => "primitive",
+                ParamTypes::Object(_) => "object",
+                ParamTypes::Array(_) => "array",
+                ParamTypes::Anyof(_) => "any_of",
+                ParamTypes::OneOf(_) => "one_of",
+                ParamTypes::AllOf(_) => "all_of",
+                ParamTypes::EnumValues(_) => "enum",
+            })
+            .collect();
+
+        for variant in [
+            "primitive",
+            "object",
+            "array",
+            "any_of",
+            "one_of",
+            "all_of",
+            "enum",
+        ] {
+            assert!(param_types.contains(&variant), "missing '{variant}' param");
+        }
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
 };
 
 /// Resolves a $ref path to its target value in the schema.
-fn resolve_ref<'a>(
+pub fn resolve_ref<'a>(
     ref_path: &str,
     root_schema: &'a Value,
     visited: &mut HashSet<String>,
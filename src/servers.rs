@@ -0,0 +1,131 @@
+//! Module for loading the MCP servers config file, used to render several servers into one document.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use serde::Deserialize;
+
+use crate::error::{DiscoveryError, DiscoveryResult};
+
+/// Command launching an MCP server over stdio, along with its environment variables.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ServerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+impl ServerConfig {
+    /// Creates a server config from a launch command and its arguments, as given on the command line.
+    pub fn from_command(command: &[String]) -> Self {
+        Self {
+            command: command.first().cloned().unwrap_or_default(),
+            args: command.iter().skip(1).cloned().collect(),
+            env: HashMap::new(),
+        }
+    }
+
+    /// Returns the command and its arguments, as one list.
+    pub fn launch_command(&self) -> Vec<String> {
+        std::iter::once(self.command.to_owned())
+            .chain(self.args.iter().cloned())
+            .collect()
+    }
+}
+
+/// MCP servers config file, in the `mcpServers` format shared by most MCP clients:
+/// ```json
+/// { "mcpServers": { "github": { "command": "npx", "args": ["-y", "server-github"], "env": {} } } }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct ServersConfig {
+    #[serde(rename = "mcpServers", default)]
+    pub mcp_servers: BTreeMap<String, ServerConfig>,
+}
+
+impl ServersConfig {
+    /// Loads a config file. Files with a `.yaml` or `.yml` extension are parsed as YAML, any other file as JSON.
+    pub fn load(config_file: &Path) -> DiscoveryResult<Self> {
+        let content = std::fs::read_to_string(config_file).map_err(|error| {
+            DiscoveryError::ReadFile(config_file.to_string_lossy().into_owned(), error)
+        })?;
+        let is_yaml = matches!(
+            config_file.extension().and_then(|ext| ext.to_str()),
+            Some("yaml" | "yml")
+        );
+        let config = if is_yaml {
+            serde_yaml::from_str(&content).map_err(|error| error.to_string())
+        } else {
+            serde_json::from_str(&content).map_err(|error| error.to_string())
+        };
+        config.map_err(|error| {
+            DiscoveryError::InvalidConfig(format!(
+                "Failed to parse '{}': {error}",
+                config_file.display()
+            ))
+        })
+    }
+
+    /// Returns the config of a server by name.
+    pub fn server(&self, name: &str) -> DiscoveryResult<&ServerConfig> {
+        self.mcp_servers.get(name).ok_or_else(|| {
+            DiscoveryError::InvalidConfig(format!(
+                "Server '{name}' is not defined in the servers config. Available servers: {}",
+                self.mcp_servers
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_servers_config() {
+        let dir = TempDir::new().unwrap();
+
+        let json_file = dir.path().join("mcp.json");
+        write(
+            &json_file,
+            r#"{ "mcpServers": { "github": { "command": "npx", "args": ["-y", "server-github"], "env": { "TOKEN": "x" } }, "time": { "command": "uvx" } } }"#,
+        )
+        .unwrap();
+        let config = ServersConfig::load(&json_file).unwrap();
+        let github = config.server("github").unwrap();
+        assert_eq!(github.launch_command(), vec!["npx", "-y", "server-github"]);
+        assert_eq!(github.env.get("TOKEN").map(String::as_str), Some("x"));
+        assert!(config.server("time").unwrap().args.is_empty());
+
+        let error = config.server("slack").unwrap_err().to_string();
+        assert!(error.contains("Available servers: github, time"), "{error}");
+
+        let yaml_file = dir.path().join("mcp.yaml");
+        write(
+            &yaml_file,
+            "mcpServers:\n  time:\n    command: uvx\n    args: [mcp-server-time]\n",
+        )
+        .unwrap();
+        let config = ServersConfig::load(&yaml_file).unwrap();
+        assert_eq!(
+            config.server("time").unwrap(),
+            &ServerConfig::from_command(&["uvx".to_string(), "mcp-server-time".to_string()])
+        );
+
+        write(&json_file, "{ not json").unwrap();
+        assert!(matches!(
+            ServersConfig::load(&json_file),
+            Err(DiscoveryError::InvalidConfig(_))
+        ));
+    }
+}
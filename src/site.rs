@@ -0,0 +1,298 @@
+//! Module for generating a multi-page static documentation site from MCP server information.
+//!
+//! Every page type is rendered through a Handlebars partial (`site-index`, `site-tool`,
+//! `site-prompt`, `site-resource-template`, sharing `site-layout` and `site-pager`), so a
+//! custom `<partial-name>.hbs` file in a template directory can replace any of them.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    error::DiscoveryResult, render_template::handlebars_registry, utils::slugify, McpServerInfo,
+};
+
+/// Names of the partials used to render the site, which can be overridden from a template directory.
+pub const SITE_PARTIALS: [&str; 6] = [
+    "site-layout",
+    "site-pager",
+    "site-index",
+    "site-tool",
+    "site-prompt",
+    "site-resource-template",
+];
+
+/// Name of the client-side search index file written to the root of the site.
+pub const SEARCH_INDEX_FILE: &str = "search-index.js";
+
+/// A single generated file, with its path relative to the site output directory.
+#[derive(Debug)]
+pub struct SitePage {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// A link to a generated page, used for navigation, cross-links and the search index.
+#[derive(Serialize, Clone, Debug)]
+struct SiteLink {
+    name: String,
+    kind: &'static str,
+    url: String,
+    description: String,
+}
+
+/// Builds links for a list of items, ensuring each item gets a unique file name within `dir`.
+fn site_links<'a>(
+    kind: &'static str,
+    dir: &str,
+    items: impl Iterator<Item = (&'a str, Option<&'a String>)>,
+) -> Vec<SiteLink> {
+    let mut used = HashSet::new();
+    items
+        .map(|(name, description)| {
+            let base = match slugify(name) {
+                slug if slug.is_empty() => kind.to_string(),
+                slug => slug,
+            };
+            let mut slug = base.clone();
+            let mut counter = 2;
+            while !used.insert(slug.clone()) {
+                slug = format!("{base}-{counter}");
+                counter += 1;
+            }
+            SiteLink {
+                name: name.to_string(),
+                kind,
+                url: format!("{dir}/{slug}.html"),
+                description: description.cloned().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Builds the render context of a page: the server info at the root, plus `site` navigation
+/// details and, for detail pages, the item being documented under `item_key`.
+fn page_context(server_value: &Value, site: Value, item: Option<(&str, Value)>) -> Value {
+    let mut context = server_value.clone();
+    if let Value::Object(map) = &mut context {
+        map.insert("site".to_string(), site);
+        if let Some((item_key, item_value)) = item {
+            map.insert(item_key.to_string(), item_value);
+        }
+    }
+    context
+}
+
+/// Renders all pages of the documentation site for the given server information.
+///
+/// Partials found in `template_dir` as `<partial-name>.hbs` (see [`SITE_PARTIALS`]) override
+/// the built-in ones.
+pub fn render_site(
+    server_info: &McpServerInfo,
+    template_dir: Option<&Path>,
+) -> DiscoveryResult<Vec<SitePage>> {
+    let mut handlebar = handlebars_registry();
+
+    if let Some(template_dir) = template_dir {
+        for name in SITE_PARTIALS {
+            let partial_file = template_dir.join(format!("{name}.hbs"));
+            if partial_file.exists() {
+                tracing::trace!("Using '{}' for {}", partial_file.display(), name);
+                let content = std::fs::read_to_string(&partial_file)?;
+                handlebar
+                    .register_partial(name, content)
+                    .map_err(handlebars::RenderError::from)?;
+            }
+        }
+    }
+
+    let tools = server_info.tools.as_deref().unwrap_or_default();
+    let prompts = server_info.prompts.as_deref().unwrap_or_default();
+    let resources = server_info.resources.as_deref().unwrap_or_default();
+    let resource_templates = server_info
+        .resource_templates
+        .as_deref()
+        .unwrap_or_default();
+
+    let tool_links = site_links(
+        "tool",
+        "tools",
+        tools
+            .iter()
+            .map(|t| (t.name.as_str(), t.description.as_ref())),
+    );
+    let prompt_links = site_links(
+        "prompt",
+        "prompts",
+        prompts
+            .iter()
+            .map(|p| (p.name.as_str(), p.description.as_ref())),
+    );
+    let resource_template_links = site_links(
+        "resource template",
+        "resource-templates",
+        resource_templates
+            .iter()
+            .map(|r| (r.name.as_str(), r.description.as_ref())),
+    );
+
+    let nav = json!({
+        "tools": tool_links,
+        "prompts": prompt_links,
+        "resource_templates": resource_template_links,
+    });
+
+    let server_value = serde_json::to_value(server_info)?;
+    let mut pages = vec![];
+
+    let index_context = page_context(&server_value, json!({ "root": "", "nav": nav }), None);
+    pages.push(SitePage {
+        path: PathBuf::from("index.html"),
+        content: handlebar.render_template("{{> site-index }}", &index_context)?,
+    });
+
+    let detail_pages: [(&str, &str, Vec<Value>, &Vec<SiteLink>); 3] = [
+        (
+            "site-tool",
+            "tool",
+            tools
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<_, _>>()?,
+            &tool_links,
+        ),
+        (
+            "site-prompt",
+            "prompt",
+            prompts
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<_, _>>()?,
+            &prompt_links,
+        ),
+        (
+            "site-resource-template",
+            "resource_template",
+            resource_templates
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<_, _>>()?,
+            &resource_template_links,
+        ),
+    ];
+
+    for (partial, item_key, items, links) in detail_pages {
+        for (index, (item, link)) in items.into_iter().zip(links.iter()).enumerate() {
+            let site = json!({
+                "root": "../",
+                "nav": nav,
+                "page_title": link.name,
+                "previous": index.checked_sub(1).and_then(|i| links.get(i)),
+                "next": links.get(index + 1),
+            });
+            let context = page_context(&server_value, site, Some((item_key, item)));
+            pages.push(SitePage {
+                path: PathBuf::from(&link.url),
+                content: handlebar.render_template(&format!("{{{{> {partial} }}}}"), &context)?,
+            });
+        }
+    }
+
+    // resources have no page of their own, search results point to the index page
+    let resource_links = resources.iter().map(|r| SiteLink {
+        name: r.name.to_owned(),
+        kind: "resource",
+        url: "index.html".to_string(),
+        description: r.description.to_owned().unwrap_or_default(),
+    });
+
+    let search_index: Vec<SiteLink> = tool_links
+        .iter()
+        .chain(prompt_links.iter())
+        .chain(resource_template_links.iter())
+        .cloned()
+        .chain(resource_links)
+        .collect();
+
+    pages.push(SitePage {
+        path: PathBuf::from(SEARCH_INDEX_FILE),
+        content: format!(
+            "window.MCP_SEARCH_INDEX = {};\n",
+            serde_json::to_string(&search_index)?
+        ),
+    });
+
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_site_pages() {
+        let mut server_info = sample_server_info().unwrap();
+        // a tool whose name has the same slug as another one gets a numbered page
+        let tools = server_info.tools.as_mut().unwrap();
+        let mut duplicate = tools[0].clone();
+        duplicate.name = "Search_Issues".to_string();
+        tools.push(duplicate);
+
+        let pages = render_site(&server_info, None).expect("Failed to render site");
+        let paths: Vec<_> = pages.iter().map(|p| p.path.to_string_lossy()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "index.html",
+                "tools/search_issues.html",
+                "tools/delete_issue.html",
+                "tools/search_issues-2.html",
+                "prompts/summarize_issue.html",
+                "prompts/greeting.html",
+                "resource-templates/issue.html",
+                SEARCH_INDEX_FILE
+            ]
+        );
+
+        let index = &pages[0].content;
+        assert!(index
+            .contains(r#"<a href="tools/search_issues-2.html"><code>Search_Issues</code></a>"#));
+        assert!(index.contains(r#"<script src="search-index.js"></script>"#));
+
+        let tool_page = &pages[1].content;
+        assert!(tool_page.contains("<title>search_issues - sample-server 1.0.0</title>"));
+        assert!(tool_page.contains(r#"<a href="../index.html">Index</a>"#));
+        assert!(
+            tool_page.contains(r#"<a href="../tools/delete_issue.html">delete_issue</a> &rarr;"#)
+        );
+        assert!(tool_page.contains(r#"<a href="../prompts/greeting.html">greeting</a>"#));
+
+        let search_index = &pages[7].content;
+        assert!(search_index.starts_with("window.MCP_SEARCH_INDEX = ["));
+        assert!(search_index.contains(r#""name":"readme","kind":"resource","url":"index.html""#));
+    }
+
+    #[test]
+    fn test_render_site_template_override() {
+        let template_dir = TempDir::new().unwrap();
+        std::fs::write(
+            template_dir.path().join("site-prompt.hbs"),
+            "custom {{prompt.name}} page",
+        )
+        .unwrap();
+
+        let pages = render_site(&sample_server_info().unwrap(), Some(template_dir.path()))
+            .expect("Failed to render site");
+        let prompt_page = pages
+            .iter()
+            .find(|p| p.path == Path::new("prompts/greeting.html"))
+            .unwrap();
+        assert_eq!(prompt_page.content, "custom greeting page");
+    }
+}
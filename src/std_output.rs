@@ -206,6 +206,7 @@ mod tests {
         let info = McpServerInfo {
             name: "MyApp".to_string(),
             version: "0.9".to_string(),
+            protocol_version: None,
             capabilities: McpCapabilities {
                 tools: true,
                 prompts: false,
@@ -223,6 +224,9 @@ mod tests {
             prompts: None,
             resources: None,
             resource_templates: None,
+            resource_contents: None,
+            prompt_samples: None,
+            completion_samples: None,
             title: None,
             description: None,
             website_url: None,
@@ -252,6 +256,7 @@ mod tests {
         let info = McpServerInfo {
             name: "SampleServer".to_string(),
             version: "1.1".to_string(),
+            protocol_version: None,
             capabilities: McpCapabilities {
                 tools: true,
                 prompts: true,
@@ -269,6 +274,9 @@ mod tests {
             prompts: None,
             resources: None,
             resource_templates: None,
+            resource_contents: None,
+            prompt_samples: None,
+            completion_samples: None,
             title: None,
             description: None,
             website_url: None,
@@ -1,12 +1,14 @@
 use crate::{
-    error::DiscoveryResult,
+    error::{DiscoveryError, DiscoveryResult},
     render_template,
-    types::Template,
+    types::{ExportOptions, RenderOptions, Template, TemplateSection},
     utils::{find_template_file, line_ending},
     McpServerInfo,
 };
+use regex::Regex;
 use std::{
     borrow::Cow,
+    io::{self, ErrorKind},
     path::{Path, PathBuf},
 };
 
@@ -16,6 +18,11 @@ const TEMPLATE_HTML: &str = include_str!("../templates/html/html_template.html")
 const TEMPLATE_MARKDOWN_PLAIN: &str =
     include_str!("../templates/markdown/markdown_plain_template.md");
 const TEMPLATE_TEXT: &str = include_str!("../templates/text/text_template.txt");
+const TEMPLATE_MAN: &str = include_str!("../templates/man/man_template.7");
+const TEMPLATE_MERMAID: &str = include_str!("../templates/diagram/mermaid_template.md");
+const TEMPLATE_DOT: &str = include_str!("../templates/diagram/dot_template.dot");
+const TEMPLATE_LLMS: &str = include_str!("../templates/llms/llms_template.txt");
+const TEMPLATE_LLMS_FULL: &str = include_str!("../templates/llms/llms_full_template.txt");
 
 pub const TITLE_VERSION: &str = include_str!("../templates/common/title.hbs");
 pub const MD_SUMMARY: &str = include_str!("../templates/markdown/summary.hbs");
@@ -35,6 +42,7 @@ pub const MD_PLAIN_RESOURCE_TEMPLATES: &str =
     include_str!("../templates/markdown/md_plain_resource_templates.hbs");
 
 // html partials
+pub const HTML_STYLE: &str = include_str!("../templates/html/html_style.hbs");
 pub const HTML_SUMMARY: &str = include_str!("../templates/html/html_summary.hbs");
 pub const HTML_TOOLS: &str = include_str!("../templates/html/html_tools.hbs");
 pub const HTML_PROMPTS: &str = include_str!("../templates/html/html_prompts.hbs");
@@ -50,7 +58,25 @@ pub const TEXT_RESOURCES: &str = include_str!("../templates/text/text_resources.
 pub const TEXT_RESOURCE_TEMPLATES: &str =
     include_str!("../templates/text/text_resource_templates.hbs");
 
-pub static PARTIALS: [(&str, &str); 21] = [
+// man partials
+pub const MAN_SUMMARY: &str = include_str!("../templates/man/man_summary.hbs");
+pub const MAN_TOOLS: &str = include_str!("../templates/man/man_tools.hbs");
+pub const MAN_PROMPTS: &str = include_str!("../templates/man/man_prompts.hbs");
+pub const MAN_RESOURCES: &str = include_str!("../templates/man/man_resources.hbs");
+
+// diagram partials
+pub const MERMAID_GRAPH: &str = include_str!("../templates/diagram/mermaid_graph.hbs");
+
+// site partials, one per page type
+pub const SITE_LAYOUT: &str = include_str!("../templates/site/site_layout.hbs");
+pub const SITE_PAGER: &str = include_str!("../templates/site/site_pager.hbs");
+pub const SITE_INDEX: &str = include_str!("../templates/site/site_index.hbs");
+pub const SITE_TOOL: &str = include_str!("../templates/site/site_tool.hbs");
+pub const SITE_PROMPT: &str = include_str!("../templates/site/site_prompt.hbs");
+pub const SITE_RESOURCE_TEMPLATE: &str =
+    include_str!("../templates/site/site_resource_template.hbs");
+
+pub static PARTIALS: [(&str, &str); 33] = [
     ("title-version", TITLE_VERSION),
     ("summary", MD_SUMMARY),
     ("md-tools", MD_TOOLS),
@@ -61,6 +87,7 @@ pub static PARTIALS: [(&str, &str); 21] = [
     ("md-plain-prompts", MD_PLAIN_PROMPTS),
     ("md-plain-resources", MD_PLAIN_RESOURCES),
     ("md-plain-resource-templates", MD_PLAIN_RESOURCE_TEMPLATES),
+    ("html-style", HTML_STYLE),
     ("html-summary", HTML_SUMMARY),
     ("html-tools", HTML_TOOLS),
     ("html-prompts", HTML_PROMPTS),
@@ -72,13 +99,33 @@ pub static PARTIALS: [(&str, &str); 21] = [
     ("txt-resources", TEXT_RESOURCES),
     ("txt-resource-templates", TEXT_RESOURCE_TEMPLATES),
     ("txt-summary", TEXT_SUMMARY),
+    ("man-summary", MAN_SUMMARY),
+    ("man-tools", MAN_TOOLS),
+    ("man-prompts", MAN_PROMPTS),
+    ("man-resources", MAN_RESOURCES),
+    ("mermaid-graph", MERMAID_GRAPH),
+    ("site-layout", SITE_LAYOUT),
+    ("site-pager", SITE_PAGER),
+    ("site-index", SITE_INDEX),
+    ("site-tool", SITE_TOOL),
+    ("site-prompt", SITE_PROMPT),
+    ("site-resource-template", SITE_RESOURCE_TEMPLATE),
 ];
 
+/// Name of the directory, inside the export directory, where partials of an exported template are written
+pub const EXPORT_PARTIALS_DIR: &str = "partials";
+
+/// Regex matching partial calls such as `{{> md-tools}}`, `{{~> summary}}` or `{{#> site-layout}}`
+const PARTIAL_CALL_REGEX: &str = r"\{\{~?#?>\s*([\w-]+)";
+
 /// Struct to hold information about inline templates
 /// Used for templates embedded within other content with specific markers
 #[derive(Debug)]
 pub struct InlineTemplateInfo {
+    /// Template content, without the comment prefix of its lines
     pub template: String,
+    /// Template lines as found in the file, preserved when the file is updated
+    pub source: String,
     pub marker_start: String,
     pub marker_end: String,
 }
@@ -95,12 +142,24 @@ pub enum OutputTemplate {
     Txt,
     /// MD Plain template
     MdPlain,
+    /// Man page (roff) template
+    Man,
+    /// Mermaid flowchart of the server capabilities, wrapped in a markdown code block
+    Mermaid,
+    /// Graphviz (dot) graph of the server capabilities
+    Dot,
+    /// Compact llms.txt style description for LLM agents
+    Llms,
+    /// Expanded llms-full.txt style description for LLM agents
+    LlmsFull,
     /// Custom template from file
     CustomTemplate(PathBuf),
     /// Template from string
     TemplateString(String),
     /// Inline template with markers
     InlineTemplate(InlineTemplateInfo),
+    /// A single built-in partial, rendered on its own
+    Partial(&'static str),
     // Print to the terminal
     None,
 }
@@ -114,39 +173,128 @@ impl OutputTemplate {
         Ok(OutputTemplate::CustomTemplate(actual_template_file))
     }
 
+    /// Returns the file name of a built-in template, as found in the `templates` folder
+    pub fn builtin_file_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Md => Some("markdown_template.md"),
+            Self::MdPlain => Some("markdown_plain_template.md"),
+            Self::Html => Some("html_template.html"),
+            Self::Txt => Some("text_template.txt"),
+            Self::Man => Some("man_template.7"),
+            Self::Mermaid => Some("mermaid_template.md"),
+            Self::Dot => Some("dot_template.dot"),
+            Self::Llms => Some("llms_template.txt"),
+            Self::LlmsFull => Some("llms_full_template.txt"),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the built-in partial rendering a section of the template,
+    /// or `None` if the template has no such section
+    pub fn section_partial(&self, section: &TemplateSection) -> Option<&'static str> {
+        let partial = match (self, section) {
+            (Self::Md | Self::MdPlain, TemplateSection::Summary) => "summary",
+            (Self::Md, TemplateSection::Tools) => "md-tools",
+            (Self::Md, TemplateSection::Prompts) => "md-prompts",
+            (Self::Md, TemplateSection::Resources) => "md-resources",
+            (Self::Md, TemplateSection::ResourceTemplates) => "md-resource-templates",
+            (Self::MdPlain, TemplateSection::Tools) => "md-plain-tools",
+            (Self::MdPlain, TemplateSection::Prompts) => "md-plain-prompts",
+            (Self::MdPlain, TemplateSection::Resources) => "md-plain-resources",
+            (Self::MdPlain, TemplateSection::ResourceTemplates) => "md-plain-resource-templates",
+            (Self::Html, TemplateSection::Summary) => "html-summary",
+            (Self::Html, TemplateSection::Tools) => "html-tools",
+            (Self::Html, TemplateSection::Prompts) => "html-prompts",
+            (Self::Html, TemplateSection::Resources) => "html-resources",
+            (Self::Html, TemplateSection::ResourceTemplates) => "html-resource-templates",
+            (Self::Txt, TemplateSection::Summary) => "txt-summary",
+            (Self::Txt, TemplateSection::Tools) => "txt-tools",
+            (Self::Txt, TemplateSection::Prompts) => "txt-prompts",
+            (Self::Txt, TemplateSection::Resources) => "txt-resources",
+            (Self::Txt, TemplateSection::ResourceTemplates) => "txt-resource-templates",
+            // the man page lists resource templates within its resources section
+            (Self::Man, TemplateSection::Summary) => "man-summary",
+            (Self::Man, TemplateSection::Tools) => "man-tools",
+            (Self::Man, TemplateSection::Prompts) => "man-prompts",
+            (Self::Man, TemplateSection::Resources) => "man-resources",
+            _ => return None,
+        };
+        Some(partial)
+    }
+
+    /// Returns the built-in partials used by the template, including partials used by other partials
+    pub fn partials(&self) -> DiscoveryResult<Vec<(&'static str, &'static str)>> {
+        let regex = Regex::new(PARTIAL_CALL_REGEX)?;
+        let mut partials: Vec<(&'static str, &'static str)> = vec![];
+        let mut pending: Vec<String> = vec![self.content()?.into_owned()];
+
+        while let Some(content) = pending.pop() {
+            for name in regex.captures_iter(&content).map(|c| c[1].to_string()) {
+                if partials.iter().any(|(existing, _)| *existing == name) {
+                    continue;
+                }
+                if let Some(partial) = PARTIALS.iter().find(|(partial, _)| *partial == name) {
+                    partials.push(*partial);
+                    pending.push(partial.1.to_string());
+                }
+            }
+        }
+
+        Ok(partials)
+    }
+
+    /// Returns a name identifying the template in render diagnostics
+    pub fn name(&self) -> String {
+        match self {
+            Self::CustomTemplate(path_buf) => path_buf.to_string_lossy().into_owned(),
+            Self::TemplateString(_) => "template-string".to_string(),
+            Self::InlineTemplate(_) => "inline-template".to_string(),
+            // registered apart from the partial itself, which it includes
+            Self::Partial(name) => format!("section-{name}"),
+            Self::None => "".to_string(),
+            _ => self.builtin_file_name().unwrap_or_default().to_string(),
+        }
+    }
+
     /// Returns the content of the template as a Cow string
-    pub fn content(&self) -> Cow<'_, str> {
-        match &self {
+    /// Returns an error if a custom template file cannot be read
+    pub fn content(&self) -> DiscoveryResult<Cow<'_, str>> {
+        let content = match &self {
             // Return borrowed references to static templates
             Self::Md => Cow::Borrowed(TEMPLATE_MARKDOWN),
             Self::MdPlain => Cow::Borrowed(TEMPLATE_MARKDOWN_PLAIN),
             Self::Html => Cow::Borrowed(TEMPLATE_HTML),
             Self::Txt => Cow::Borrowed(TEMPLATE_TEXT),
-            // Read custom template from file, return error message instead of the template if reading fails
+            Self::Man => Cow::Borrowed(TEMPLATE_MAN),
+            Self::Mermaid => Cow::Borrowed(TEMPLATE_MERMAID),
+            Self::Dot => Cow::Borrowed(TEMPLATE_DOT),
+            Self::Llms => Cow::Borrowed(TEMPLATE_LLMS),
+            Self::LlmsFull => Cow::Borrowed(TEMPLATE_LLMS_FULL),
             Self::CustomTemplate(path_buf) => {
-                let content = std::fs::read_to_string(path_buf).unwrap_or(format!(
-                    ">> ERROR LOADING TEMPLATE FILE : '{}' <<",
-                    path_buf.to_string_lossy()
-                ));
+                let content = std::fs::read_to_string(path_buf).map_err(|error| {
+                    DiscoveryError::LoadTemplate(path_buf.to_string_lossy().into_owned(), error)
+                })?;
                 Cow::Owned(content)
             }
             Self::TemplateString(template_str) => Cow::Owned(template_str.to_owned()),
             OutputTemplate::InlineTemplate(inline_template_info) => {
                 Cow::Owned(inline_template_info.template.to_owned())
             }
+            Self::Partial(name) => Cow::Owned(format!("{{{{> {name}}}}}")),
             Self::None => Cow::Owned("".into()),
-        }
+        };
+        Ok(content)
     }
 
     /// Generates formatted inline template, including markers and proper line endings
     /// Used for InlineTemplate variant to format the output with start/end markers
-    fn inline_template(&self, inline_template_info: &InlineTemplateInfo) -> String {
-        let line_ending = line_ending(&inline_template_info.template, None);
+    pub(crate) fn inline_template(&self, inline_template_info: &InlineTemplateInfo) -> String {
+        let line_ending = line_ending(&inline_template_info.source, None);
         format!(
             "{}{}{}{}{}{}",
             inline_template_info.marker_start,
             line_ending,
-            inline_template_info.template,
+            inline_template_info.source,
             line_ending,
             inline_template_info.marker_end,
             line_ending,
@@ -156,7 +304,17 @@ impl OutputTemplate {
     /// Renders the template with provided server information
     /// Returns the rendered output as a `DiscoveryResult<String>`
     pub fn render_template(&self, server_info: &McpServerInfo) -> DiscoveryResult<String> {
-        let rendered = render_template(self, server_info)?;
+        self.render_with_options(server_info, &RenderOptions::default())
+    }
+
+    /// Renders the template with provided server information and render options,
+    /// such as user-defined partials and strict mode
+    pub fn render_with_options(
+        &self,
+        server_info: &McpServerInfo,
+        render_options: &RenderOptions,
+    ) -> DiscoveryResult<String> {
+        let rendered = render_template(self, server_info, render_options)?;
         match self {
             OutputTemplate::InlineTemplate(inline_template_info) => Ok(format!(
                 "{}{}",
@@ -175,6 +333,11 @@ impl From<&Template> for OutputTemplate {
             Template::Html => OutputTemplate::Html,
             Template::Txt => OutputTemplate::Txt,
             Template::MdPlain => OutputTemplate::MdPlain,
+            Template::Man => OutputTemplate::Man,
+            Template::Mermaid => OutputTemplate::Mermaid,
+            Template::Dot => OutputTemplate::Dot,
+            Template::Llms => OutputTemplate::Llms,
+            Template::LlmsFull => OutputTemplate::LlmsFull,
         }
     }
 }
@@ -186,6 +349,155 @@ impl From<Template> for OutputTemplate {
             Template::Html => OutputTemplate::Html,
             Template::Txt => OutputTemplate::Txt,
             Template::MdPlain => OutputTemplate::MdPlain,
+            Template::Man => OutputTemplate::Man,
+            Template::Mermaid => OutputTemplate::Mermaid,
+            Template::Dot => OutputTemplate::Dot,
+            Template::Llms => OutputTemplate::Llms,
+            Template::LlmsFull => OutputTemplate::LlmsFull,
+        }
+    }
+}
+
+/// Writes a built-in template and all the partials it uses to `out_dir`, returning the written paths.
+/// The template keeps its original file name and partials are written to `partials/<partial-name>.hbs`,
+/// a layout that can be used as-is with `--template-file` and `--partials-dir`.
+pub fn export_template(options: &ExportOptions) -> DiscoveryResult<Vec<PathBuf>> {
+    let template: OutputTemplate = (&options.template).into();
+    let partials_dir = options.out_dir.join(EXPORT_PARTIALS_DIR);
+
+    let mut files: Vec<(PathBuf, Cow<'_, str>)> = vec![(
+        options
+            .out_dir
+            .join(template.builtin_file_name().unwrap_or_default()),
+        template.content()?,
+    )];
+    files.extend(template.partials()?.into_iter().map(|(name, content)| {
+        (
+            partials_dir.join(format!("{name}.hbs")),
+            Cow::Borrowed(content),
+        )
+    }));
+
+    if !options.force {
+        if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "File '{}' already exists, use --force to overwrite it",
+                    path.to_string_lossy()
+                ),
+            )
+            .into());
         }
     }
+
+    std::fs::create_dir_all(&partials_dir)?;
+    for (path, content) in files.iter() {
+        std::fs::write(path, content.as_bytes())?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+
+    #[test]
+    fn test_template_partials() {
+        let names: Vec<_> = OutputTemplate::Md
+            .partials()
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names.len(), 6);
+        for name in [
+            "title-version",
+            "summary",
+            "md-tools",
+            "md-prompts",
+            "md-resources",
+            "md-resource-templates",
+        ] {
+            assert!(names.contains(&name), "missing partial '{name}'");
+        }
+
+        assert!(OutputTemplate::Llms.partials().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_section_partial() {
+        let sections = [
+            TemplateSection::Summary,
+            TemplateSection::Tools,
+            TemplateSection::Prompts,
+            TemplateSection::Resources,
+            TemplateSection::ResourceTemplates,
+        ];
+        // every section partial is a partial used by the template itself
+        for template in [
+            OutputTemplate::Md,
+            OutputTemplate::MdPlain,
+            OutputTemplate::Html,
+            OutputTemplate::Txt,
+            OutputTemplate::Man,
+        ] {
+            let partials = template.partials().unwrap();
+            for partial in sections.iter().filter_map(|s| template.section_partial(s)) {
+                assert!(partials.iter().any(|(name, _)| *name == partial));
+            }
+        }
+
+        assert_eq!(
+            OutputTemplate::Html.section_partial(&TemplateSection::Tools),
+            Some("html-tools")
+        );
+        assert_eq!(
+            OutputTemplate::Man.section_partial(&TemplateSection::ResourceTemplates),
+            None
+        );
+        assert_eq!(
+            OutputTemplate::Mermaid.section_partial(&TemplateSection::Tools),
+            None
+        );
+    }
+
+    #[test]
+    fn test_export_template() {
+        let out_dir = tempfile::TempDir::new().unwrap();
+        let options = ExportOptions {
+            template: Template::Html,
+            out_dir: out_dir.path().to_path_buf(),
+            force: false,
+        };
+
+        let files = export_template(&options).unwrap();
+        let template_file = out_dir.path().join("html_template.html");
+        let partials_dir = out_dir.path().join(EXPORT_PARTIALS_DIR);
+        assert_eq!(files[0], template_file);
+        assert!(partials_dir.join("html-style.hbs").exists());
+        assert!(partials_dir.join("title-version.hbs").exists());
+
+        // exported files render exactly like the built-in template
+        let server_info = sample_server_info().unwrap();
+        let render_options = RenderOptions {
+            partials_dir: Some(partials_dir),
+            ..Default::default()
+        };
+        let exported = OutputTemplate::CustomTemplate(template_file)
+            .render_with_options(&server_info, &render_options)
+            .unwrap();
+        let builtin = OutputTemplate::Html.render_template(&server_info).unwrap();
+        assert_eq!(exported, builtin);
+
+        // existing files are only overwritten with `force`
+        assert!(export_template(&options).is_err());
+        let options = ExportOptions {
+            force: true,
+            ..options
+        };
+        assert!(export_template(&options).is_ok());
+    }
 }
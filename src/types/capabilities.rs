@@ -1,7 +1,8 @@
 use rust_mcp_sdk::schema::{
-    Icon, Prompt, Resource, ResourceTemplate, ToolAnnotations, ToolExecution, ToolInputSchema,
+    Icon, Prompt, PromptMessage, Resource, ResourceTemplate, ToolAnnotations, ToolExecution,
+    ToolInputSchema,
 };
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
 #[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug)]
 pub struct McpTaskSupport {
@@ -160,6 +161,9 @@ pub struct McpServerInfo {
     #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
     pub website_url: Option<String>,
     pub version: String,
+    /// MCP protocol version negotiated with the server
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol_version: Option<String>,
     pub capabilities: McpCapabilities,
     #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
     pub tools: Option<Vec<McpToolMeta>>,
@@ -169,4 +173,68 @@ pub struct McpServerInfo {
     pub resources: Option<Vec<Resource>>,
     #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
     pub resource_templates: Option<Vec<ResourceTemplate>>,
+    /// Previews of the content of resources, keyed by resource URI. Only collected with `--read-resources`.
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub resource_contents: Option<BTreeMap<String, McpResourceContent>>,
+    /// Samples of the prompts, keyed by prompt name. Only collected with `--prompt-samples`.
+    /// Kept apart from `prompts`, which holds the prompts as listed by the server, like `resource_contents`.
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub prompt_samples: Option<BTreeMap<String, McpPromptSample>>,
+    /// Completion values of prompt arguments and resource template variables.
+    /// Only collected when the server supports completions.
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub completion_samples: Option<McpCompletionSamples>,
+}
+
+/// Completion values suggested by the server for an empty prefix, used to document valid values.
+#[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug, Default, PartialEq)]
+pub struct McpCompletionSamples {
+    /// Completion values of prompt arguments, keyed by prompt name, then by argument name.
+    #[serde(default)]
+    pub prompts: BTreeMap<String, BTreeMap<String, McpCompletionValues>>,
+    /// Completion values of resource template variables, keyed by template name, then by variable name.
+    #[serde(default)]
+    pub resource_templates: BTreeMap<String, BTreeMap<String, McpCompletionValues>>,
+}
+
+/// Completion values of an argument or variable, as returned by `completion/complete`.
+#[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpCompletionValues {
+    pub values: Vec<String>,
+    /// Total number of values available, which may exceed the values returned.
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub total: Option<i64>,
+    /// True if more values are available than the values returned.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// Sample of a prompt, got with `prompts/get` during discovery using sample or placeholder arguments.
+#[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug)]
+pub struct McpPromptSample {
+    /// Arguments the prompt was got with.
+    pub arguments: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub description: Option<String>,
+    /// Messages the prompt expands to.
+    pub messages: Vec<PromptMessage>,
+}
+
+/// Preview of the content of a resource, read with `resources/read` during discovery.
+/// Text content is kept up to the size limit, binary content is only summarized.
+#[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResourceContent {
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Size of the content in bytes, decoded for binary content.
+    pub size: usize,
+    /// SHA-256 hash of the content, as a hexadecimal string.
+    pub sha256: String,
+    /// Text of the content, truncated to the size limit. Not set for binary content.
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub text: Option<String>,
+    /// True if the text was truncated to the size limit.
+    pub truncated: bool,
 }
@@ -5,9 +5,11 @@ use std::{
     str::FromStr,
 };
 
+use serde_json::{Map, Value};
+
 use crate::{
     error::{DiscoveryError, DiscoveryResult},
-    utils::match_template,
+    utils::{load_vars, match_template},
     OutputTemplate,
 };
 
@@ -18,8 +20,25 @@ pub enum DiscoveryCommand {
     Print(PrintOptions),
     /// Creates a file with MCP server capability details.
     Create(WriteOptions),
-    /// Updates a file by adding MCP server capability information between specified markers.
-    Update(WriteOptions),
+    /// Updates files by adding MCP server capability information between specified markers.
+    Update(UpdateOptions),
+    /// Generates a multi-page static HTML documentation site.
+    Site(SiteOptions),
+    /// Writes SVG badges summarizing the server capabilities.
+    Badges(BadgeOptions),
+    /// Calls a tool of the MCP server and prints its result.
+    Call(CallOptions),
+    /// Starts an interactive session to browse and use the capabilities of the MCP server.
+    Explore(ExploreOptions),
+}
+
+/// Enum representing template maintenance actions, which do not launch an MCP server.
+#[derive(Debug)]
+pub enum TemplatesCommand {
+    /// Writes a built-in template and its partials to disk for customization.
+    Export(ExportOptions),
+    /// Checks a custom template by rendering it against a sample server.
+    Validate(ValidateOptions),
 }
 
 /// Enum defining the types of built-in templates supported for output formatting.
@@ -29,6 +48,11 @@ pub enum Template {
     MdPlain,
     Html,
     Txt,
+    Man,
+    Mermaid,
+    Dot,
+    Llms,
+    LlmsFull,
 }
 
 impl FromStr for Template {
@@ -39,11 +63,43 @@ impl FromStr for Template {
             "md-plain" => Ok(Template::MdPlain),
             "html" => Ok(Template::Html),
             "txt" => Ok(Template::Txt),
+            "man" => Ok(Template::Man),
+            "mermaid" => Ok(Template::Mermaid),
+            "dot" => Ok(Template::Dot),
+            "llms" => Ok(Template::Llms),
+            "llms-full" => Ok(Template::LlmsFull),
             _ => Err(DiscoveryError::InvalidTemplate(s.to_string())),
         }
     }
 }
 
+/// Enum representing the sections of a built-in template, which can be rendered on their own
+/// using the `section` property of a render marker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateSection {
+    Summary,
+    Tools,
+    Prompts,
+    Resources,
+    ResourceTemplates,
+}
+
+impl FromStr for TemplateSection {
+    type Err = DiscoveryError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "summary" => Ok(TemplateSection::Summary),
+            "tools" => Ok(TemplateSection::Tools),
+            "prompts" => Ok(TemplateSection::Prompts),
+            "resources" => Ok(TemplateSection::Resources),
+            "resource-templates" => Ok(TemplateSection::ResourceTemplates),
+            _ => Err(DiscoveryError::ParseTemplate(format!(
+                "'{s}' is not a valid section! Expected one of: summary, tools, prompts, resources, resource-templates."
+            ))),
+        }
+    }
+}
+
 /// Enum representing supported log levels for controlling output verbosity.
 #[derive(Debug, Clone, PartialEq)]
 #[allow(non_camel_case_types)]
@@ -67,6 +123,96 @@ impl Display for LogLevel {
     }
 }
 
+/// Enum defining the formats used to preview changes with `--dry-run`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum DiffFormat {
+    /// Colored unified diff, for the terminal.
+    #[default]
+    Unified,
+    /// One JSON object per file, for use by other tools.
+    Json,
+}
+
+/// Options controlling how a template is rendered.
+#[derive(Debug, Default, Clone)]
+pub struct RenderOptions {
+    /// Directory of `*.hbs` files registered as partials, overriding built-in partials of the same name.
+    pub partials_dir: Option<PathBuf>,
+
+    /// Enables Handlebars strict mode, failing on missing fields instead of rendering them as empty text.
+    pub strict: bool,
+
+    /// User-defined variables, available to templates under the `vars` namespace.
+    pub vars: Map<String, Value>,
+}
+
+/// Options controlling what is collected from the MCP server during discovery, beyond its listings.
+#[derive(Debug, Default, Clone)]
+pub struct DiscoveryOptions {
+    /// Reads the content of the listed resources, storing a preview of each one.
+    pub read_resources: bool,
+
+    /// Maximum number of bytes kept in the text preview of a resource.
+    pub resource_size_limit: usize,
+
+    /// Comma separated glob or regex patterns of the URIs of the resources to read. All resources are read when not set.
+    pub resource_include: Option<String>,
+
+    /// Comma separated glob or regex patterns of the URIs of the resources not to read.
+    pub resource_exclude: Option<String>,
+
+    /// Gets each prompt with sample arguments, storing the messages it expands to.
+    pub prompt_samples: bool,
+
+    /// JSON or YAML file of sample prompt arguments, as an object of argument values for each prompt name.
+    /// Required arguments without a sample value are set to a `<name>` placeholder.
+    pub prompt_args_file: Option<PathBuf>,
+}
+
+/// Options controlling how the selected template is rendered, shared by the commands rendering a template.
+#[derive(Debug, Default, Clone)]
+pub struct TemplateOptions {
+    /// Directory of `*.hbs` files registered as partials, overriding built-in partials of the same name.
+    pub partials_dir: Option<PathBuf>,
+
+    /// Fails on missing fields and helpers instead of rendering them as empty text.
+    pub strict: bool,
+
+    /// Template variables defined as `key=value` pairs, available under `vars`.
+    pub defines: Vec<(String, String)>,
+
+    /// JSON or YAML file of template variables, available under `vars`.
+    pub vars_file: Option<PathBuf>,
+}
+
+impl TemplateOptions {
+    /// Returns the options used to render the selected template,
+    /// loading template variables from the variables file and definitions.
+    pub fn render_options(&self) -> DiscoveryResult<RenderOptions> {
+        Ok(RenderOptions {
+            partials_dir: self.partials_dir.to_owned(),
+            strict: self.strict,
+            vars: load_vars(self.vars_file.as_deref(), &self.defines)?,
+        })
+    }
+}
+
+/// Options controlling how rendered files are written by the `Create` and `Update` variants of `DiscoveryCommand`.
+#[derive(Debug, Default, Clone)]
+pub struct OutputOptions {
+    /// Prints the changes instead of writing files.
+    pub dry_run: bool,
+
+    /// Format used to print the changes of a dry run.
+    pub diff_format: DiffFormat,
+
+    /// Keeps a copy of an overwritten file, with a `.bak` extension appended.
+    pub backup: bool,
+
+    /// Paths watched for changes, re-running discovery and rendering on change. Watch mode is off when empty.
+    pub watch: Vec<PathBuf>,
+}
+
 /// Options used when running the `Print` variant of `DiscoveryCommand`.
 #[derive(Debug)]
 pub struct PrintOptions {
@@ -79,6 +225,12 @@ pub struct PrintOptions {
     /// Template content provided as a string.
     pub template_string: Option<String>,
 
+    /// Options controlling how the selected template is rendered.
+    pub templating: TemplateOptions,
+
+    /// Options controlling what is collected from the MCP server during discovery.
+    pub discovery: DiscoveryOptions,
+
     /// Specifies the logging level for the application (default: info)
     pub log_level: Option<LogLevel>,
 
@@ -98,8 +250,8 @@ impl PrintOptions {
     }
 }
 
-/// Options used when running the `Create` or `Update` variants of `DiscoveryCommand`.
-#[derive(Debug)]
+/// Options used when running the `Create` variant of `DiscoveryCommand`, or updating a single file.
+#[derive(Debug, Default)]
 pub struct WriteOptions {
     pub filename: PathBuf,
 
@@ -112,6 +264,18 @@ pub struct WriteOptions {
     /// Template content provided as a string.
     pub template_string: Option<String>,
 
+    /// Options controlling how the selected template is rendered.
+    pub templating: TemplateOptions,
+
+    /// Options controlling how rendered files are written.
+    pub output: OutputOptions,
+
+    /// Reads files that are not valid UTF-8 as Latin-1, instead of failing.
+    pub latin1_fallback: bool,
+
+    /// Options controlling what is collected from the MCP server during discovery.
+    pub discovery: DiscoveryOptions,
+
     /// Specifies the logging level for the application (default: info)
     pub log_level: Option<LogLevel>,
     /// Command and arguments to launch the MCP server.
@@ -141,6 +305,197 @@ impl WriteOptions {
     }
 }
 
+/// Status of a file processed by the `Update` variant of `DiscoveryCommand`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateStatus {
+    /// The file was rewritten with the rendered capabilities.
+    Updated,
+    /// The rendered capabilities were already up to date, the file was left untouched.
+    Unchanged,
+    /// The file contains no render markers.
+    NoMarkers,
+}
+
+impl Display for UpdateStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            UpdateStatus::Updated => "updated",
+            UpdateStatus::Unchanged => "unchanged",
+            UpdateStatus::NoMarkers => "no markers",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// Options used when running the `Update` variant of `DiscoveryCommand`.
+#[derive(Debug)]
+pub struct UpdateOptions {
+    /// Files to update, as paths or glob patterns.
+    pub filenames: Vec<String>,
+
+    /// Select an output template from the built-in options.
+    pub template: Option<Template>,
+
+    /// Path to a custom template file written in the Handlebars format.
+    pub template_file: Option<PathBuf>,
+
+    /// Template content provided as a string.
+    pub template_string: Option<String>,
+
+    /// Options controlling how the selected template is rendered.
+    pub templating: TemplateOptions,
+
+    /// Options controlling how rendered files are written.
+    pub output: OutputOptions,
+
+    /// Reads files that are not valid UTF-8 as Latin-1, instead of failing.
+    pub latin1_fallback: bool,
+
+    /// MCP servers config file, providing the servers selected by the `server` property of render markers.
+    pub config: Option<PathBuf>,
+
+    /// Options controlling what is collected from the MCP server during discovery.
+    pub discovery: DiscoveryOptions,
+
+    /// Specifies the logging level for the application (default: info)
+    pub log_level: Option<LogLevel>,
+    /// Command and arguments to launch the MCP server.
+    pub mcp_server_cmd: Vec<String>,
+}
+
+impl UpdateOptions {
+    /// Returns the files to update, in the given order and without duplicates.
+    /// Glob patterns are expanded to the matching files, other values are used as-is.
+    pub fn files(&self) -> DiscoveryResult<Vec<PathBuf>> {
+        let mut files: Vec<PathBuf> = vec![];
+        for filename in self.filenames.iter() {
+            let matches = if filename.contains(['*', '?', '[']) {
+                let paths = glob::glob(filename).map_err(|error| {
+                    DiscoveryError::InvalidPattern(format!(
+                        "Invalid glob pattern '{filename}': {error}"
+                    ))
+                })?;
+                paths
+                    .filter_map(Result::ok)
+                    .filter(|path| path.is_file())
+                    .collect()
+            } else {
+                vec![PathBuf::from(filename)]
+            };
+
+            for file in matches {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Returns the options used to update one of the files.
+    pub fn file_options(&self, filename: PathBuf) -> WriteOptions {
+        WriteOptions {
+            filename,
+            template: self.template.to_owned(),
+            template_file: self.template_file.to_owned(),
+            template_string: self.template_string.to_owned(),
+            templating: self.templating.to_owned(),
+            output: self.output.to_owned(),
+            latin1_fallback: self.latin1_fallback,
+            discovery: self.discovery.to_owned(),
+            log_level: self.log_level.to_owned(),
+            mcp_server_cmd: self.mcp_server_cmd.to_owned(),
+        }
+    }
+}
+
+/// Options used when running the `Site` variant of `DiscoveryCommand`.
+#[derive(Debug)]
+pub struct SiteOptions {
+    /// Directory where the generated site will be written.
+    pub out_dir: PathBuf,
+
+    /// Directory containing `<partial-name>.hbs` files that override built-in site page templates.
+    pub template_dir: Option<PathBuf>,
+
+    /// Options controlling what is collected from the MCP server during discovery.
+    pub discovery: DiscoveryOptions,
+
+    /// Specifies the logging level for the application (default: info)
+    pub log_level: Option<LogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    pub mcp_server_cmd: Vec<String>,
+}
+
+/// Options used when running the `Badges` variant of `DiscoveryCommand`.
+#[derive(Debug)]
+pub struct BadgeOptions {
+    /// Directory where the SVG badges will be written.
+    pub out_dir: PathBuf,
+
+    /// Options controlling what is collected from the MCP server during discovery.
+    pub discovery: DiscoveryOptions,
+
+    /// Specifies the logging level for the application (default: info)
+    pub log_level: Option<LogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    pub mcp_server_cmd: Vec<String>,
+}
+
+/// Options used when running the `Call` variant of `DiscoveryCommand`.
+#[derive(Debug)]
+pub struct CallOptions {
+    /// Name of the tool to call.
+    pub tool: String,
+
+    /// Tool arguments as a JSON object.
+    pub args: Option<String>,
+
+    /// Tool arguments as `key=value` pairs, coerced to the type of each parameter.
+    pub arg: Vec<(String, String)>,
+
+    /// Specifies the logging level for the application (default: info)
+    pub log_level: Option<LogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    pub mcp_server_cmd: Vec<String>,
+}
+
+/// Options used when running the `Explore` variant of `DiscoveryCommand`.
+#[derive(Debug)]
+pub struct ExploreOptions {
+    /// Specifies the logging level for the application (default: info)
+    pub log_level: Option<LogLevel>,
+
+    /// Command and arguments to launch the MCP server.
+    pub mcp_server_cmd: Vec<String>,
+}
+
+/// Options used when running the `Export` variant of `TemplatesCommand`.
+#[derive(Debug)]
+pub struct ExportOptions {
+    /// Built-in template to export.
+    pub template: Template,
+
+    /// Directory where the template and its partials will be written.
+    pub out_dir: PathBuf,
+
+    /// Overwrite files that already exist in the output directory.
+    pub force: bool,
+}
+
+/// Options used when running the `Validate` variant of `TemplatesCommand`.
+#[derive(Debug)]
+pub struct ValidateOptions {
+    /// Path to the Handlebars template file to validate.
+    pub template_file: PathBuf,
+
+    /// Options controlling how the template is rendered against the sample server.
+    pub templating: TemplateOptions,
+}
+
 impl DiscoveryCommand {
     /// Retrieves the MCP server launch command for the current variant.
     pub fn mcp_launch_command(&self) -> &Vec<String> {
@@ -148,6 +503,77 @@ impl DiscoveryCommand {
             DiscoveryCommand::Create(create_options) => &create_options.mcp_server_cmd,
             DiscoveryCommand::Update(update_options) => &update_options.mcp_server_cmd,
             DiscoveryCommand::Print(print_args) => &print_args.mcp_server_cmd,
+            DiscoveryCommand::Site(site_options) => &site_options.mcp_server_cmd,
+            DiscoveryCommand::Badges(badge_options) => &badge_options.mcp_server_cmd,
+            DiscoveryCommand::Call(call_options) => &call_options.mcp_server_cmd,
+            DiscoveryCommand::Explore(explore_options) => &explore_options.mcp_server_cmd,
+        }
+    }
+
+    /// Returns true if the command prints machine-readable output, which launch messages must not mix with.
+    pub fn json_output(&self) -> bool {
+        match self {
+            DiscoveryCommand::Create(WriteOptions { output, .. })
+            | DiscoveryCommand::Update(UpdateOptions { output, .. }) => {
+                output.dry_run && output.diff_format == DiffFormat::Json
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the paths watched in watch mode, or an empty list when the command does not watch files.
+    ///
+    /// Along with the paths given on the command line, the template, partials, variables and config files
+    /// are watched, as well as the target documents.
+    pub fn watched_paths(&self) -> DiscoveryResult<Vec<PathBuf>> {
+        let (watch, extra_paths) = match self {
+            DiscoveryCommand::Create(create_options) => (
+                &create_options.output.watch,
+                vec![
+                    create_options.template_file.to_owned(),
+                    create_options.templating.partials_dir.to_owned(),
+                    create_options.templating.vars_file.to_owned(),
+                    create_options.discovery.prompt_args_file.to_owned(),
+                    Some(create_options.filename.to_owned()),
+                ],
+            ),
+            DiscoveryCommand::Update(update_options) if !update_options.output.watch.is_empty() => {
+                (
+                    &update_options.output.watch,
+                    [
+                        update_options.template_file.to_owned(),
+                        update_options.templating.partials_dir.to_owned(),
+                        update_options.templating.vars_file.to_owned(),
+                        update_options.config.to_owned(),
+                        update_options.discovery.prompt_args_file.to_owned(),
+                    ]
+                    .into_iter()
+                    .chain(update_options.files()?.into_iter().map(Some))
+                    .collect(),
+                )
+            }
+            _ => return Ok(vec![]),
+        };
+
+        if watch.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(watch
+            .iter()
+            .cloned()
+            .chain(extra_paths.into_iter().flatten())
+            .collect())
+    }
+
+    /// Retrieves the discovery options of the current variant, if it discovers the server capabilities.
+    pub fn discovery_options(&self) -> Option<&DiscoveryOptions> {
+        match self {
+            DiscoveryCommand::Create(create_options) => Some(&create_options.discovery),
+            DiscoveryCommand::Update(update_options) => Some(&update_options.discovery),
+            DiscoveryCommand::Print(print_args) => Some(&print_args.discovery),
+            DiscoveryCommand::Site(site_options) => Some(&site_options.discovery),
+            DiscoveryCommand::Badges(badge_options) => Some(&badge_options.discovery),
+            DiscoveryCommand::Call(_) | DiscoveryCommand::Explore(_) => None,
         }
     }
 
@@ -157,6 +583,78 @@ impl DiscoveryCommand {
             DiscoveryCommand::Create(create_options) => &create_options.log_level,
             DiscoveryCommand::Update(update_options) => &update_options.log_level,
             DiscoveryCommand::Print(print_args) => &print_args.log_level,
+            DiscoveryCommand::Site(site_options) => &site_options.log_level,
+            DiscoveryCommand::Badges(badge_options) => &badge_options.log_level,
+            DiscoveryCommand::Call(call_options) => &call_options.log_level,
+            DiscoveryCommand::Explore(explore_options) => &explore_options.log_level,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, write};
+
+    #[test]
+    fn test_update_options_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for package in ["a", "b"] {
+            create_dir_all(dir.path().join("packages").join(package)).unwrap();
+            write(
+                dir.path().join("packages").join(package).join("README.md"),
+                "",
+            )
+            .unwrap();
+        }
+        create_dir_all(dir.path().join("packages/c/README.md")).unwrap();
+        let root_readme = dir.path().join("README.md");
+
+        let options = UpdateOptions {
+            filenames: vec![
+                root_readme.to_string_lossy().into_owned(),
+                dir.path()
+                    .join("packages/*/README.md")
+                    .to_string_lossy()
+                    .into_owned(),
+                dir.path()
+                    .join("packages/a/README.md")
+                    .to_string_lossy()
+                    .into_owned(),
+            ],
+            template: None,
+            template_file: None,
+            template_string: None,
+            templating: Default::default(),
+            output: Default::default(),
+            latin1_fallback: false,
+            config: None,
+            discovery: Default::default(),
+            log_level: None,
+            mcp_server_cmd: vec!["mcp-server".to_string()],
+        };
+
+        // plain paths are kept even if missing, globs only match files and duplicates are removed
+        assert_eq!(
+            options.files().unwrap(),
+            vec![
+                root_readme.clone(),
+                dir.path().join("packages/a/README.md"),
+                dir.path().join("packages/b/README.md"),
+            ]
+        );
+        assert_eq!(
+            options.file_options(root_readme.clone()).filename,
+            root_readme
+        );
+
+        let options = UpdateOptions {
+            filenames: vec!["docs/[*.md".to_string()],
+            ..options
+        };
+        assert!(matches!(
+            options.files(),
+            Err(DiscoveryError::InvalidPattern(_))
+        ));
+    }
+}
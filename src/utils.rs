@@ -1,8 +1,17 @@
 use path_clean::PathClean;
 
-use crate::{error::DiscoveryResult, types::Template, OutputTemplate};
+use crate::{
+    encoding::TextEncoding,
+    error::{DiscoveryError, DiscoveryResult},
+    types::Template,
+    OutputTemplate,
+};
+use regex::Regex;
+use serde_json::{Map, Value};
 use std::{
-    io::{self, ErrorKind},
+    borrow::Cow,
+    fs,
+    io::{self, ErrorKind, Write},
     path::{Path, PathBuf},
 };
 
@@ -16,10 +25,45 @@ pub struct RenderTemplateInfo {
 pub struct UpdateTemplateInfo {
     /// Content of the file to be updated by mcp-discovery
     pub content: String,
+    /// Encoding of the file, used to write the updated content back
+    pub encoding: TextEncoding,
     pub line_ending: String,
     pub render_locations: Vec<RenderTemplateInfo>,
 }
 
+impl UpdateTemplateInfo {
+    /// Returns the content of the file, with each render location replaced by its rendered template.
+    ///
+    /// Lines outside the render locations are kept byte for byte, including a byte order mark,
+    /// their own line endings and the trailing newline. Rendered lines use the line ending
+    /// of the line preceding them.
+    pub fn updated_content(&self) -> String {
+        let mut content_lines: Vec<Cow<'_, str>> = self
+            .content
+            .split_inclusive('\n')
+            .map(Cow::Borrowed)
+            .collect();
+
+        for location in self.render_locations.iter().rev() {
+            let (start, end) = location.render_location;
+            let line_ending = content_lines
+                .get(start - 1)
+                .map(|line| if line.ends_with("\r\n") { "\r\n" } else { "\n" })
+                .unwrap_or(&self.line_ending);
+
+            content_lines.splice(
+                start..end - 1,
+                location
+                    .rendered_template
+                    .lines()
+                    .map(|line| Cow::Owned(format!("{line}{line_ending}"))),
+            );
+        }
+
+        content_lines.concat()
+    }
+}
+
 pub fn line_ending(content: &str, line_number: Option<usize>) -> &str {
     let line_number = line_number.unwrap_or(1);
     let target = line_number.saturating_sub(1); // 0-based index
@@ -51,6 +95,13 @@ pub fn match_template(
     }
 
     if let Some(filename) = filename {
+        // llms.txt conventions are identified by the file name rather than the extension
+        match filename.file_name().and_then(|f| f.to_str()) {
+            Some("llms.txt") => return Ok(OutputTemplate::Llms),
+            Some("llms-full.txt") => return Ok(OutputTemplate::LlmsFull),
+            _ => {}
+        }
+
         // detect appropriate template based on the file extension, default to txt
         let extension = filename
             .extension()
@@ -62,6 +113,8 @@ pub fn match_template(
             "txt" => Ok(OutputTemplate::Txt),
             "md" | "markdown" | "mdown" | "mkd" | "mdtxt" | "mdtext" => Ok(OutputTemplate::Md),
             "htm" | "html" => Ok(OutputTemplate::Html),
+            "7" | "man" => Ok(OutputTemplate::Man),
+            "dot" | "gv" => Ok(OutputTemplate::Dot),
             _ => Ok(OutputTemplate::Txt),
         }
     } else {
@@ -70,6 +123,152 @@ pub fn match_template(
     }
 }
 
+/// Follows the symbolic links of a path, returning the path of the file they point to.
+/// A dangling link resolves to the missing file it points to.
+fn resolve_symlinks(path: &Path) -> io::Result<PathBuf> {
+    let mut resolved = path.to_path_buf();
+    // the limit stops on circular links
+    for _ in 0..40 {
+        match fs::symlink_metadata(&resolved) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                let target = fs::read_link(&resolved)?;
+                resolved = match resolved.parent() {
+                    Some(parent) => parent.join(target),
+                    None => target,
+                };
+            }
+            _ => return Ok(resolved),
+        }
+    }
+    Err(io::Error::new(
+        ErrorKind::InvalidInput,
+        format!("'{}' has too many levels of symbolic links", path.display()),
+    ))
+}
+
+/// Writes a file atomically, by writing a temporary file in the same directory and renaming it
+/// over the target, so the file is never left partially written.
+/// A symbolic link is kept, and the file it points to is written instead.
+/// With `backup`, an existing file is first copied next to it with a `.bak` extension appended.
+pub fn write_atomic(path: &Path, content: impl AsRef<[u8]>, backup: bool) -> DiscoveryResult<()> {
+    let target = resolve_symlinks(path)?;
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("'{}' is not a file path", path.display()),
+            )
+        })?
+        .to_string_lossy();
+    let temp_path = target.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    let write_temp = || -> io::Result<()> {
+        let mut temp_file = fs::File::create(&temp_path)?;
+        temp_file.write_all(content.as_ref())?;
+        temp_file.sync_all()?;
+        // keep the permissions of the file being replaced
+        if let Ok(metadata) = fs::metadata(&target) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        Ok(())
+    };
+
+    if let Err(error) = write_temp() {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.into());
+    }
+
+    if backup && path.exists() {
+        let mut backup_path = path.as_os_str().to_owned();
+        backup_path.push(".bak");
+        fs::copy(path, backup_path)?;
+    }
+
+    if let Err(error) = fs::rename(&temp_path, &target) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Converts a name into a lowercase, URL and file-name friendly slug.
+/// Any run of characters other than ASCII letters, digits, `_` and `-` becomes a single `-`.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Converts a glob pattern into an anchored regular expression.
+/// `*` matches any run of characters and `?` matches a single character.
+pub fn glob_to_regex(pattern: &str) -> DiscoveryResult<Regex> {
+    let mut regex = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Ok(Regex::new(&regex)?)
+}
+
+/// Loads template variables from an optional JSON or YAML file, then applies `key=value` definitions on top.
+/// Files with a `.yaml` or `.yml` extension are parsed as YAML, any other file as JSON.
+pub fn load_vars(
+    vars_file: Option<&Path>,
+    defines: &[(String, String)],
+) -> DiscoveryResult<Map<String, Value>> {
+    let mut vars = match vars_file {
+        Some(vars_file) => {
+            let content = std::fs::read_to_string(vars_file).map_err(|error| {
+                DiscoveryError::ReadFile(vars_file.to_string_lossy().into_owned(), error)
+            })?;
+            let is_yaml = matches!(
+                vars_file.extension().and_then(|ext| ext.to_str()),
+                Some("yaml" | "yml")
+            );
+            let parsed = if is_yaml {
+                serde_yaml::from_str(&content).map_err(|error| error.to_string())
+            } else {
+                serde_json::from_str(&content).map_err(|error| error.to_string())
+            };
+            let value: Value = parsed.map_err(|error| {
+                DiscoveryError::InvalidVars(format!(
+                    "Failed to parse '{}': {error}",
+                    vars_file.display()
+                ))
+            })?;
+            match value {
+                Value::Object(map) => map,
+                _ => {
+                    return Err(DiscoveryError::InvalidVars(format!(
+                        "Variables file '{}' must contain an object of key/value pairs.",
+                        vars_file.display()
+                    )))
+                }
+            }
+        }
+        None => Map::new(),
+    };
+
+    vars.extend(
+        defines
+            .iter()
+            .map(|(key, value)| (key.to_owned(), Value::String(value.to_owned()))),
+    );
+
+    Ok(vars)
+}
+
 pub fn boolean_indicator(boolean: bool) -> char {
     match boolean {
         true => '✔',
@@ -122,3 +321,95 @@ pub fn find_template_file(
         .into(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("README.md");
+
+        write_atomic(&file, "first\r\n", true).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "first\r\n");
+        assert!(!dir.path().join("README.md.bak").exists());
+
+        write_atomic(&file, "second\n", true).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "second\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("README.md.bak")).unwrap(),
+            "first\r\n"
+        );
+
+        // no temporary file is left behind
+        let entries: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries.len(), 2, "{entries:?}");
+    }
+
+    #[test]
+    fn test_load_vars_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        // parse errors of both formats name the file
+        for (name, content) in [("vars.json", "{\"pkg\": "), ("vars.yaml", "pkg: [")] {
+            let vars_file = dir.path().join(name);
+            fs::write(&vars_file, content).unwrap();
+            let error = load_vars(Some(&vars_file), &[]).unwrap_err();
+            assert!(matches!(error, DiscoveryError::InvalidVars(_)), "{name}");
+            assert!(
+                error
+                    .to_string()
+                    .contains(&format!("Failed to parse '{}'", vars_file.display())),
+                "{error}"
+            );
+        }
+
+        let vars_file = dir.path().join("missing.json");
+        assert!(matches!(
+            load_vars(Some(&vars_file), &[]),
+            Err(DiscoveryError::ReadFile(..))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_symlink() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("docs")).unwrap();
+        let target = dir.path().join("docs/real.md");
+        let link = dir.path().join("link.md");
+        fs::write(&target, "old\n").unwrap();
+        std::os::unix::fs::symlink("docs/real.md", &link).unwrap();
+
+        write_atomic(&link, "new\n", true).unwrap();
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("link.md.bak")).unwrap(),
+            "old\n"
+        );
+
+        // a dangling link creates the file it points to
+        let dangling = dir.path().join("dangling.md");
+        std::os::unix::fs::symlink("docs/new.md", &dangling).unwrap();
+        write_atomic(&dangling, "created\n", false).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("docs/new.md")).unwrap(),
+            "created\n"
+        );
+
+        // the temporary files are created next to the targets and removed
+        let entries: Vec<_> = fs::read_dir(dir.path().join("docs"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries.len(), 2, "{entries:?}");
+    }
+}
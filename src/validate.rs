@@ -0,0 +1,324 @@
+//! Module for validating custom templates without launching an MCP server.
+
+use std::path::Path;
+
+use handlebars::template::{Parameter, Template, TemplateElement};
+use serde_json::Value;
+
+use crate::{
+    error::DiscoveryResult,
+    render_template::{handlebars_registry, helper_names, register_partials_dir, render_template},
+    sample::sample_server_info,
+    types::RenderOptions,
+    OutputTemplate,
+};
+
+/// Helpers and partials referenced in a template, with the line and column of each reference.
+#[derive(Default)]
+struct TemplateReferences {
+    helpers: Vec<(String, (usize, usize))>,
+    partials: Vec<(String, (usize, usize))>,
+    inline_partials: Vec<String>,
+}
+
+impl TemplateReferences {
+    /// Collects references from the elements of a template and its nested blocks.
+    fn collect(&mut self, template: &Template) {
+        for (element, mapping) in template.elements.iter().zip(template.mapping.iter()) {
+            self.collect_element(element, (mapping.0, mapping.1));
+        }
+    }
+
+    fn collect_element(&mut self, element: &TemplateElement, position: (usize, usize)) {
+        match element {
+            TemplateElement::Expression(helper) | TemplateElement::HtmlExpression(helper) => {
+                // an expression without params may be a field, which is only known while rendering
+                if !helper.params.is_empty() || !helper.hash.is_empty() {
+                    self.push_helper(&helper.name, position);
+                }
+                self.collect_params(helper.params.iter().chain(helper.hash.values()), position);
+            }
+            TemplateElement::HelperBlock(helper) => {
+                self.push_helper(&helper.name, position);
+                self.collect_params(helper.params.iter().chain(helper.hash.values()), position);
+                for block in [&helper.template, &helper.inverse].into_iter().flatten() {
+                    self.collect(block);
+                }
+            }
+            TemplateElement::DecoratorExpression(decorator)
+            | TemplateElement::DecoratorBlock(decorator) => {
+                if decorator.name.as_name() == Some("inline") {
+                    if let Some(Parameter::Literal(Value::String(name))) = decorator.params.first()
+                    {
+                        self.inline_partials.push(name.to_owned());
+                    }
+                } else {
+                    self.push_helper(&decorator.name, position);
+                }
+                if let Some(block) = &decorator.template {
+                    self.collect(block);
+                }
+            }
+            TemplateElement::PartialExpression(partial)
+            | TemplateElement::PartialBlock(partial) => {
+                let name = match &partial.name {
+                    Parameter::Literal(Value::String(name)) => Some(name.as_str()),
+                    name => name.as_name(),
+                };
+                // `@partial-block` and dynamic partial names can only be resolved while rendering
+                if let Some(name) = name.filter(|name| !name.starts_with('@')) {
+                    self.partials.push((name.to_owned(), position));
+                }
+                self.collect_params(partial.params.iter().chain(partial.hash.values()), position);
+                if let Some(block) = &partial.template {
+                    self.collect(block);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_params<'a>(
+        &mut self,
+        params: impl Iterator<Item = &'a Parameter>,
+        position: (usize, usize),
+    ) {
+        for param in params {
+            if let Parameter::Subexpression(subexpression) = param {
+                if let TemplateElement::Expression(helper) = subexpression.element.as_ref() {
+                    // a sub-expression always calls a helper, even without params
+                    self.push_helper(&helper.name, position);
+                    self.collect_params(helper.params.iter().chain(helper.hash.values()), position);
+                }
+            }
+        }
+    }
+
+    fn push_helper(&mut self, name: &Parameter, position: (usize, usize)) {
+        if let Some(name) = name.as_name() {
+            self.helpers.push((name.to_owned(), position));
+        }
+    }
+}
+
+/// Validates a template file without launching an MCP server.
+///
+/// The template is parsed, the partials and helpers it references are checked, and it is rendered
+/// against a comprehensive sample server. Returns the problems found, which is empty for a valid template.
+pub fn validate_template(
+    template_file: &Path,
+    render_options: &RenderOptions,
+) -> DiscoveryResult<Vec<String>> {
+    let template = OutputTemplate::CustomTemplate(template_file.to_path_buf());
+    let template_name = template.name();
+    let content = template.content()?;
+
+    let mut handlebar = handlebars_registry();
+    if let Some(partials_dir) = &render_options.partials_dir {
+        register_partials_dir(&mut handlebar, partials_dir)?;
+    }
+
+    if let Err(error) = handlebar.register_template_string(&template_name, content) {
+        return Ok(vec![error.to_string().trim_end().to_string()]);
+    }
+
+    let mut references = TemplateReferences::default();
+    if let Some(parsed) = handlebar.get_template(&template_name) {
+        references.collect(parsed);
+    }
+
+    let helpers = helper_names();
+    let mut unresolved: Vec<((usize, usize), String)> = references
+        .helpers
+        .iter()
+        .filter(|(name, _)| !helpers.contains(&name.as_str()))
+        .map(|(name, position)| (*position, format!("Unknown helper '{name}'")))
+        .collect();
+
+    unresolved.extend(
+        references
+            .partials
+            .iter()
+            .filter(|(name, _)| {
+                !handlebar.has_template(name) && !references.inline_partials.contains(name)
+            })
+            .map(|(name, position)| (*position, format!("Unknown partial '{name}'"))),
+    );
+
+    // problems are reported in the order they appear in the template
+    unresolved.sort_by_key(|(position, _)| *position);
+    let mut problems: Vec<String> = unresolved
+        .into_iter()
+        .map(|((line, column), problem)| format!("{template_name}:{line}:{column}: {problem}"))
+        .collect();
+
+    // rendering is only meaningful once all references are resolved
+    if problems.is_empty() {
+        if let Err(error) = render_template(&template, &sample_server_info()?, render_options) {
+            problems.push(error.to_string());
+        }
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::McpServerInfo;
+    use crate::{
+        templates::{export_template, EXPORT_PARTIALS_DIR},
+        types::{ExportOptions, Template},
+    };
+    use std::fs::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_template() {
+        let dir = TempDir::new().unwrap();
+        let template_file = dir.path().join("custom.hbs");
+        let render_options = RenderOptions::default();
+
+        // a valid template, using helpers, built-in and inline partials
+        write(
+            &template_file,
+            "{{#*inline \"item\"}}- {{name}}{{/inline}}\n{{> title-version}}\n{{#each (sort_by tools \"name\")}}{{> item}}{{/each}}\n{{json capabilities}}",
+        )
+        .unwrap();
+        let problems = validate_template(&template_file, &render_options).unwrap();
+        assert!(problems.is_empty(), "{problems:?}");
+
+        // unknown helpers and partials are reported with their position
+        write(
+            &template_file,
+            "# {{name}}\n{{#each (srot_by tools \"name\")}}{{> itme}}{{/each}}",
+        )
+        .unwrap();
+        let problems = validate_template(&template_file, &render_options).unwrap();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].ends_with(":2:1: Unknown helper 'srot_by'"));
+        assert!(problems[1].ends_with("Unknown partial 'itme'"));
+
+        // problems are sorted by position, whatever their kind
+        write(&template_file, "{{> itme}}\n{{srot_by tools \"name\"}}").unwrap();
+        let problems = validate_template(&template_file, &render_options).unwrap();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].ends_with(":1:1: Unknown partial 'itme'"));
+        assert!(problems[1].ends_with(":2:1: Unknown helper 'srot_by'"));
+
+        // parse errors
+        write(&template_file, "{{#each tools}}{{/if}}").unwrap();
+        let problems = validate_template(&template_file, &render_options).unwrap();
+        assert_eq!(problems.len(), 1);
+
+        // render errors against the sample server, e.g. missing fields in strict mode
+        write(&template_file, "{{#each tools}}{{nmae}}{{/each}}").unwrap();
+        let strict = RenderOptions {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(validate_template(&template_file, &render_options)
+            .unwrap()
+            .is_empty());
+        let problems = validate_template(&template_file, &strict).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("nmae"), "{problems:?}");
+
+        // template variables are available in strict mode
+        write(&template_file, "{{vars.pkg}}").unwrap();
+        assert_eq!(validate_template(&template_file, &strict).unwrap().len(), 1);
+        let with_vars = RenderOptions {
+            vars: serde_json::from_str(r#"{"pkg": "my-server"}"#).unwrap(),
+            ..strict
+        };
+        assert!(validate_template(&template_file, &with_vars)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_builtin_templates() {
+        let dir = TempDir::new().unwrap();
+        for template in [
+            OutputTemplate::Md,
+            OutputTemplate::MdPlain,
+            OutputTemplate::Html,
+            OutputTemplate::Txt,
+            OutputTemplate::Man,
+            OutputTemplate::Mermaid,
+            OutputTemplate::Dot,
+            OutputTemplate::Llms,
+            OutputTemplate::LlmsFull,
+        ] {
+            let template_file = dir.path().join(template.builtin_file_name().unwrap());
+            write(&template_file, template.content().unwrap().as_ref()).unwrap();
+            let problems = validate_template(&template_file, &RenderOptions::default()).unwrap();
+            assert!(problems.is_empty(), "{problems:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_exported_templates_strict() {
+        // servers without any of the optional fields and data
+        let minimal_server: McpServerInfo = serde_json::from_value(serde_json::json!({
+            "name": "minimal-server",
+            "version": "1.0.0",
+            "capabilities": {
+                "tools": true, "prompts": true, "resources": true, "logging": false,
+                "completions": false, "experimental": false,
+                "task": { "tool_call_task": false, "list_task": false, "cancel_task": false }
+            },
+            "tools": [{ "name": "echo", "params": [], "input_schema": { "type": "object" }, "icons": [] }],
+            "prompts": [{ "name": "greeting", "arguments": [] }],
+            "resources": [{ "name": "readme", "uri": "file:///README.md" }],
+            "resource_templates": [{ "name": "issue", "uriTemplate": "issues://{id}" }]
+        }))
+        .unwrap();
+        let empty_server = McpServerInfo {
+            tools: None,
+            prompts: None,
+            resources: None,
+            resource_templates: None,
+            ..minimal_server.clone()
+        };
+
+        for template in [
+            Template::Md,
+            Template::MdPlain,
+            Template::Html,
+            Template::Txt,
+            Template::Man,
+            Template::Mermaid,
+            Template::Dot,
+            Template::Llms,
+            Template::LlmsFull,
+        ] {
+            let out_dir = TempDir::new().unwrap();
+            let files = export_template(&ExportOptions {
+                template: template.clone(),
+                out_dir: out_dir.path().to_path_buf(),
+                force: false,
+            })
+            .unwrap();
+
+            // exported partials are user-provided, so they are rendered in strict mode as well
+            let render_options = RenderOptions {
+                partials_dir: Some(out_dir.path().join(EXPORT_PARTIALS_DIR)),
+                strict: true,
+                ..Default::default()
+            };
+            let problems = validate_template(&files[0], &render_options).unwrap();
+            assert!(problems.is_empty(), "{problems:?}");
+
+            for server_info in [&minimal_server, &empty_server] {
+                let exported = OutputTemplate::CustomTemplate(files[0].to_owned())
+                    .render_with_options(server_info, &render_options)
+                    .unwrap();
+                let builtin = OutputTemplate::from(&template)
+                    .render_template(server_info)
+                    .unwrap();
+                assert_eq!(exported, builtin);
+            }
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Module for watching files in `--watch` mode, and summarizing the server changes between runs.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::McpServerInfo;
+
+/// Interval between two checks of the watched paths.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time the watched paths must stay unchanged before a run starts, so a burst of saves triggers a single run.
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
+
+/// Directories that are never watched, as they hold build output or dependencies.
+const IGNORED_DIRS: [&str; 2] = ["target", "node_modules"];
+
+/// Modification times of the watched files.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Collects the modification times of the files in the given paths, walking directories recursively.
+/// Hidden files and directories are skipped, as well as paths that do not exist yet.
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths.iter() {
+        collect_files(path, &mut snapshot);
+    }
+    snapshot
+}
+
+fn collect_files(path: &Path, snapshot: &mut Snapshot) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_file() {
+        if let Ok(modified) = metadata.modified() {
+            snapshot.insert(path.to_path_buf(), modified);
+        }
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || IGNORED_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        collect_files(&entry.path(), snapshot);
+    }
+}
+
+/// Returns the files added, removed or modified between two snapshots.
+pub fn changed_paths(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(modified))
+        .map(|(path, _)| path.to_owned())
+        .collect();
+    changed.extend(
+        previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+/// Updates the snapshot taken before a run with the files the run wrote, so only changes
+/// made by others during the run are detected. Written files are matched whatever the path
+/// used to reach them, e.g. relative paths or symbolic links.
+pub fn keep_written_files(previous: &mut Snapshot, current: &Snapshot, written_files: &[PathBuf]) {
+    let written_files: Vec<PathBuf> = written_files
+        .iter()
+        .map(|path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_owned()))
+        .collect();
+    for (path, modified) in current.iter() {
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+        if written_files.contains(&canonical) {
+            previous.insert(path.to_owned(), *modified);
+        }
+    }
+}
+
+/// Waits until files in the watched paths change and stay unchanged for the debounce delay.
+/// Returns the changed files, along with the snapshot to compare the next changes against.
+pub async fn wait_for_changes(paths: &[PathBuf], previous: &Snapshot) -> (Vec<PathBuf>, Snapshot) {
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let mut current = snapshot(paths);
+        if changed_paths(previous, &current).is_empty() {
+            continue;
+        }
+
+        loop {
+            tokio::time::sleep(DEBOUNCE_DELAY).await;
+            let latest = snapshot(paths);
+            if latest == current {
+                break;
+            }
+            current = latest;
+        }
+
+        let changed = changed_paths(previous, &current);
+        // files may be restored to their previous state during the debounce delay
+        if !changed.is_empty() {
+            return (changed, current);
+        }
+    }
+}
+
+/// Lists the changes between two items lists, matched by name, as `+ kind name`, `- kind name`
+/// and `~ kind name` lines.
+fn list_changes<T: Serialize>(
+    kind: &str,
+    previous: Option<&Vec<T>>,
+    current: Option<&Vec<T>>,
+    changes: &mut Vec<String>,
+) {
+    let by_name = |items: Option<&Vec<T>>| -> BTreeMap<String, Value> {
+        items
+            .into_iter()
+            .flatten()
+            .filter_map(|item| serde_json::to_value(item).ok())
+            .filter_map(|value| Some((value.get("name")?.as_str()?.to_string(), value)))
+            .collect()
+    };
+    let (previous, current) = (by_name(previous), by_name(current));
+
+    for (name, value) in current.iter() {
+        match previous.get(name) {
+            None => changes.push(format!("+ {kind} {name}")),
+            Some(previous_value) if previous_value != value => {
+                changes.push(format!("~ {kind} {name}"))
+            }
+            _ => {}
+        }
+    }
+    for name in previous.keys().filter(|name| !current.contains_key(*name)) {
+        changes.push(format!("- {kind} {name}"));
+    }
+}
+
+/// Summarizes the changes of the server between two runs, one line per change.
+/// Returns an empty list when nothing changed.
+pub fn server_changes(previous: &McpServerInfo, current: &McpServerInfo) -> Vec<String> {
+    let mut changes = vec![];
+    if previous.name != current.name {
+        changes.push(format!("~ name {} -> {}", previous.name, current.name));
+    }
+    if previous.version != current.version {
+        changes.push(format!(
+            "~ version {} -> {}",
+            previous.version, current.version
+        ));
+    }
+    if serde_json::to_value(&previous.capabilities).ok()
+        != serde_json::to_value(&current.capabilities).ok()
+    {
+        changes.push(format!("~ capabilities {}", current.capabilities));
+    }
+    list_changes(
+        "tool",
+        previous.tools.as_ref(),
+        current.tools.as_ref(),
+        &mut changes,
+    );
+    list_changes(
+        "prompt",
+        previous.prompts.as_ref(),
+        current.prompts.as_ref(),
+        &mut changes,
+    );
+    list_changes(
+        "resource",
+        previous.resources.as_ref(),
+        current.resources.as_ref(),
+        &mut changes,
+    );
+    list_changes(
+        "resource template",
+        previous.resource_templates.as_ref(),
+        current.resource_templates.as_ref(),
+        &mut changes,
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::sample_server_info;
+    use std::fs::{create_dir_all, write, File};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_changes() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        create_dir_all(src.join("nested")).unwrap();
+        create_dir_all(dir.path().join("target")).unwrap();
+        write(src.join("main.py"), "a").unwrap();
+        write(src.join("nested/tools.py"), "b").unwrap();
+        write(src.join(".main.py.swp"), "c").unwrap();
+        write(dir.path().join("target/build.log"), "d").unwrap();
+        let template = dir.path().join("template.hbs");
+
+        let paths = vec![dir.path().to_path_buf(), template.clone()];
+        let previous = snapshot(&paths);
+        // hidden files, ignored directories and missing paths are skipped
+        assert_eq!(
+            previous.keys().cloned().collect::<Vec<_>>(),
+            vec![src.join("main.py"), src.join("nested/tools.py")]
+        );
+
+        let file = File::options()
+            .write(true)
+            .open(src.join("main.py"))
+            .unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        std::fs::remove_file(src.join("nested/tools.py")).unwrap();
+        write(&template, "{{name}}").unwrap();
+
+        let current = snapshot(&paths);
+        assert_eq!(
+            changed_paths(&previous, &current),
+            vec![src.join("main.py"), src.join("nested/tools.py"), template]
+        );
+        assert!(changed_paths(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_keep_written_files() {
+        let dir = TempDir::new().unwrap();
+        let readme = dir.path().join("README.md");
+        let notes = dir.path().join("NOTES.md");
+        write(&readme, "old").unwrap();
+        write(&notes, "old").unwrap();
+        let paths = vec![dir.path().to_path_buf()];
+        let mut previous = snapshot(&paths);
+
+        // during the run, the readme and its backup are written, and the notes are edited
+        let later = SystemTime::now() + Duration::from_secs(5);
+        for file in [&readme, &notes] {
+            let file = File::options().write(true).open(file).unwrap();
+            file.set_modified(later).unwrap();
+        }
+        write(dir.path().join("README.md.bak"), "old").unwrap();
+
+        let current = snapshot(&paths);
+        let written_files = vec![
+            dir.path().join("./README.md"),
+            dir.path().join("README.md.bak"),
+        ];
+        keep_written_files(&mut previous, &current, &written_files);
+        assert_eq!(changed_paths(&previous, &current), vec![notes]);
+    }
+
+    #[test]
+    fn test_server_changes() {
+        let previous = sample_server_info().unwrap();
+        assert!(server_changes(&previous, &previous).is_empty());
+
+        let mut current = previous.clone();
+        current.version = "1.1.0".to_string();
+        let tools = current.tools.as_mut().unwrap();
+        tools[0].description = Some("Searches issues.".to_string());
+        tools.remove(1);
+        current.prompts.as_mut().unwrap()[1].name = "welcome".to_string();
+
+        assert_eq!(
+            server_changes(&previous, &current),
+            vec![
+                "~ version 1.0.0 -> 1.1.0",
+                "~ tool search_issues",
+                "- tool delete_issue",
+                "+ prompt welcome",
+                "- prompt greeting",
+            ]
+        );
+    }
+}